@@ -12,17 +12,16 @@ use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
 /// ```
 ///
 /// [`Keccak SHA3 submission`]: https://keccak.team/files/Keccak-submission-3.pdf
+#[derive(Clone)]
 pub struct Keccak {
     #[cfg(not(feature = "jolt"))]
     state: KeccakState<KeccakF>,
+    // `jolt_inlines_keccak256::Keccak256` is itself `Clone` (it is just the
+    // 200-byte sponge state), so deriving here simply clones it in place —
+    // forking a partially-absorbed hasher no longer needs a fallback buffer.
     #[cfg(feature = "jolt")]
     state: jolt_inlines_keccak256::Keccak256,
-}
-
-impl Clone for Keccak {
-    fn clone(&self) -> Self {
-        panic!("Keccak does not implement Clone");
-    }
+    output_bytes: usize,
 }
 
 impl Keccak {
@@ -57,13 +56,111 @@ impl Keccak {
     }
 
     fn new(bits: usize) -> Keccak {
+        Self::custom(bits, Self::DELIM)
+    }
+
+    /// Creates a new [`Keccak`] hasher with a security level of `bits` bits
+    /// and a custom domain-separation suffix `delim`, for building
+    /// constructions such as `TupleHash` or `KMAC` on top of the Keccak
+    /// sponge without forking this crate.
+    ///
+    /// `delim` encodes, least-significant bit first, the suffix bits appended
+    /// after the message followed by the first `1` bit of `pad10*1`; the
+    /// terminating `1` bit of the padding rule is added separately by
+    /// `finalize` and does not need to be included here. For example, the
+    /// standard Keccak value is `0x01` (no suffix bits, just the first pad
+    /// bit) and SHA-3's is `0x06` (the two suffix bits `01`, i.e. `0b10` read
+    /// LSB-first, followed by the first pad bit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is `0` or is not small enough to leave the sponge a
+    /// non-empty rate (`bits < 800`).
+    ///
+    /// Under the `jolt` feature the inlined `Keccak256` implementation only
+    /// supports the standard Keccak suffix, so this also panics if `delim`
+    /// is not [`Self::DELIM`] rather than silently hashing with the wrong
+    /// domain separation.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn custom(bits: usize, delim: u8) -> Keccak {
+        assert!(
+            bits > 0 && bits < 800,
+            "bits must be greater than 0 and less than 800"
+        );
+
+        #[cfg(feature = "jolt")]
+        assert_eq!(
+            delim,
+            Self::DELIM,
+            "the `jolt` backend only supports the standard Keccak domain-separation suffix",
+        );
+
         Keccak {
             #[cfg(not(feature = "jolt"))]
-            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+            state: KeccakState::new(bits_to_rate(bits), delim),
             #[cfg(feature = "jolt")]
             state: jolt_inlines_keccak256::Keccak256::new(),
+            output_bytes: bits / 8,
         }
     }
+
+    /// Pads, squeezes and returns the digest as a fixed-size array, checking
+    /// `N` against the security level this hasher was constructed with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not match the output length implied by the
+    /// `vNNN()` constructor used to create this hasher.
+    pub fn finalize_array<const N: usize>(self) -> [u8; N] {
+        assert_eq!(
+            N, self.output_bytes,
+            "output array length does not match the configured security level",
+        );
+        let mut output = [0u8; N];
+        self.finalize(&mut output);
+        output
+    }
+
+    /// Computes the Keccak-224 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v224()` followed by `update` and `finalize`.
+    pub fn keccak224(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v224();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-256 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v256()` followed by `update` and `finalize`.
+    pub fn keccak256(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v256();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-384 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v384()` followed by `update` and `finalize`.
+    pub fn keccak384(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v384();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-512 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v512()` followed by `update` and `finalize`.
+    pub fn keccak512(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v512();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
 }
 
 impl Hasher for Keccak {
@@ -108,4 +205,198 @@ impl Hasher for Keccak {
             output.copy_from_slice(&hash);
         }
     }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Keccak`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    fn reset(&mut self) {
+        #[cfg(not(feature = "jolt"))]
+        self.state.reset();
+
+        #[cfg(feature = "jolt")]
+        {
+            self.state = jolt_inlines_keccak256::Keccak256::new();
+        }
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        #[cfg(not(feature = "jolt"))]
+        self.state.finalize_reset(output);
+
+        #[cfg(feature = "jolt")]
+        {
+            let hash = self.state.clone().finalize();
+            output.copy_from_slice(&hash);
+            self.state = jolt_inlines_keccak256::Keccak256::new();
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "jolt")))]
+mod tests {
+    use super::*;
+
+    // Known vector: keccak256("") is the well-known "empty input" digest
+    // used throughout Ethereum (e.g. as the code hash of non-contract
+    // accounts), so it doubles as a cross-check against an independent
+    // implementation.
+    #[test]
+    fn keccak256_of_empty_input_matches_known_vector() {
+        let mut keccak = Keccak::v256();
+        let mut output = [0u8; 32];
+        keccak.update(b"");
+        keccak.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+    }
+
+    #[test]
+    fn keccak256_of_hello_matches_known_vector() {
+        let mut keccak = Keccak::v256();
+        let mut output = [0u8; 32];
+        keccak.update(b"hello");
+        keccak.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x1c, 0x8a, 0xff, 0x95, 0x06, 0x85, 0xc2, 0xed, 0x4b, 0xc3, 0x17, 0x4f, 0x34, 0x72,
+                0x28, 0x7b, 0x56, 0xd9, 0x51, 0x7b, 0x9c, 0x94, 0x81, 0x27, 0x31, 0x9a, 0x09, 0xa7,
+                0xa3, 0x6d, 0xea, 0xc8,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-224("hello").
+    #[test]
+    fn keccak224_one_shot_matches_known_vector() {
+        let mut output = [0u8; 28];
+        Keccak::keccak224(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x45, 0x52, 0x4e, 0xc4, 0x54, 0xbc, 0xc7, 0xd4, 0xb8, 0xf7, 0x43, 0x50, 0xc4, 0xa4,
+                0xe6, 0x28, 0x09, 0xfc, 0xb4, 0x9b, 0xc2, 0x9d, 0xf6, 0x2e, 0x61, 0xb6, 0x9f, 0xa4,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-384("hello").
+    #[test]
+    fn keccak384_one_shot_matches_known_vector() {
+        let mut output = [0u8; 48];
+        Keccak::keccak384(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0xdc, 0xef, 0x6f, 0xb7, 0x90, 0x8f, 0xd5, 0x2b, 0xa2, 0x6a, 0xab, 0xa7, 0x51, 0x21,
+                0x52, 0x6a, 0xbb, 0xf1, 0x21, 0x7f, 0x1c, 0x0a, 0x31, 0x02, 0x46, 0x52, 0xd1, 0x34,
+                0xd3, 0xe3, 0x2f, 0xb4, 0xcd, 0x8e, 0x9c, 0x70, 0x3b, 0x8f, 0x43, 0xe7, 0x27, 0x7b,
+                0x59, 0xa5, 0xcd, 0x40, 0x21, 0x75,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-512("hello").
+    #[test]
+    fn keccak512_one_shot_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Keccak::keccak512(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x52, 0xfa, 0x80, 0x66, 0x2e, 0x64, 0xc1, 0x28, 0xf8, 0x38, 0x9c, 0x9e, 0xa6, 0xc7,
+                0x3d, 0x4c, 0x02, 0x36, 0x80, 0x04, 0xbf, 0x44, 0x63, 0x49, 0x19, 0x00, 0xd1, 0x1a,
+                0xaa, 0xdc, 0xa3, 0x9d, 0x47, 0xde, 0x1b, 0x01, 0x36, 0x1f, 0x20, 0x7c, 0x51, 0x2c,
+                0xfa, 0x79, 0xf0, 0xf9, 0x2c, 0x33, 0x95, 0xc6, 0x7f, 0xf7, 0x92, 0x8e, 0x3f, 0x5c,
+                0xe3, 0xe3, 0xc8, 0x52, 0xb3, 0x92, 0xf9, 0x76,
+            ]
+        );
+    }
+
+    #[test]
+    fn clone_forks_a_partially_absorbed_state() {
+        let mut prefix = Keccak::v256();
+        prefix.update(b"hello");
+
+        let mut a = prefix.clone();
+        let mut b = prefix.clone();
+        a.update(b" world");
+        b.update(b" there");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello world", &mut want);
+        assert_eq!(out_a, want);
+    }
+
+    #[test]
+    fn finalize_array_matches_finalize() {
+        let mut via_finalize = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut via_finalize);
+
+        let mut keccak = Keccak::v256();
+        keccak.update(b"hello");
+        let via_array: [u8; 32] = keccak.finalize_array();
+        assert_eq!(via_array, via_finalize);
+    }
+
+    #[test]
+    #[should_panic(expected = "output array length does not match the configured security level")]
+    fn finalize_array_panics_on_mismatched_length() {
+        let _: [u8; 16] = Keccak::v256().finalize_array();
+    }
+
+    #[test]
+    fn finalize_reset_matches_finalize_then_fresh_hasher() {
+        let mut hasher = Keccak::v256();
+        hasher.update(b"hello");
+        let mut got = [0u8; 32];
+        hasher.finalize_reset(&mut got);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut want);
+        assert_eq!(got, want);
+
+        hasher.update(b"world");
+        let mut got2 = [0u8; 32];
+        hasher.finalize(&mut got2);
+        let mut want2 = [0u8; 32];
+        Keccak::keccak256(b"world", &mut want2);
+        assert_eq!(got2, want2);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn custom_with_sha3_delim_matches_sha3() {
+        // 0x06 is SHA-3's domain-separation suffix (see `custom`'s docs).
+        let mut keccak = Keccak::custom(256, 0x06);
+        keccak.update(b"hello");
+        let mut got = [0u8; 32];
+        keccak.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        crate::Sha3::sha3_256(b"hello", &mut want);
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    #[should_panic(expected = "bits must be greater than 0 and less than 800")]
+    fn custom_rejects_out_of_range_bits() {
+        Keccak::custom(10_000, Keccak::DELIM);
+    }
 }
@@ -0,0 +1,33 @@
+//! Benchmarks `Keccak::update`'s many-small-update fast path: a workload of
+//! a million 4-byte `update` calls, which spends almost all of its time in
+//! the single-block absorb rather than the multi-block loop or the
+//! permutation itself.
+//!
+//! This file has never been run through `cargo bench`: the snapshot has no
+//! `Cargo.toml` to add `criterion` as a dev-dependency or a `[[bench]]`
+//! entry to, so it's written to match the manifest that would need to
+//! exist, not validated against one.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tiny_keccak::{Hasher, Keccak};
+
+const UPDATE_COUNT: usize = 1_000_000;
+
+fn many_small_updates(c: &mut Criterion) {
+    let chunk = [0x5au8; 4];
+
+    c.bench_function("keccak256/1M x 4-byte updates", |b| {
+        b.iter(|| {
+            let mut hasher = Keccak::v256();
+            for _ in 0..UPDATE_COUNT {
+                hasher.update(black_box(&chunk));
+            }
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            black_box(output)
+        })
+    });
+}
+
+criterion_group!(benches, many_small_updates);
+criterion_main!(benches);
@@ -0,0 +1,196 @@
+//! Keccak-`f[25w]` permutations, generic over lane width.
+//!
+//! The Keccak family is defined for `f[b]` with `b = 25·2^l`, `l = 0..6`,
+//! giving lane widths `w ∈ {1, 2, 4, 8, 16, 32, 64}` and round counts
+//! `nr = 12 + 2l`. This module only supports the byte-aligned lane widths
+//! (`w` a power of two, `w >= 8`), since those are the ones representable by
+//! a Rust unsigned integer type.
+
+use crate::{Buffer, Permutation};
+
+/// An unsigned integer lane type usable in a Keccak-`f[25w]` permutation.
+///
+/// Implemented for `u8`, `u16`, `u32` and `u64`, corresponding to the
+/// `f[200]`, `f[400]`, `f[800]` and `f[1600]` members of the Keccak family.
+pub(crate) trait Lane:
+    Copy
+    + Default
+    + core::ops::BitXor<Output = Self>
+    + core::ops::BitAnd<Output = Self>
+    + core::ops::Not<Output = Self>
+{
+    /// Rotates the lane left by `n` bits.
+    fn rotate_left(self, n: u32) -> Self;
+
+    /// Truncates a 64-bit round constant to this lane's low `BITS` bits.
+    fn from_round_constant(rc: u64) -> Self;
+
+    /// Reverses the lane's byte order (used on big-endian targets).
+    #[cfg(target_endian = "big")]
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_lane {
+    ($ty:ty) => {
+        impl Lane for $ty {
+            fn rotate_left(self, n: u32) -> Self {
+                <$ty>::rotate_left(self, n % <$ty>::BITS)
+            }
+
+            fn from_round_constant(rc: u64) -> Self {
+                rc as $ty
+            }
+
+            #[cfg(target_endian = "big")]
+            fn swap_bytes(self) -> Self {
+                <$ty>::swap_bytes(self)
+            }
+        }
+    };
+}
+
+impl_lane!(u8);
+impl_lane!(u16);
+impl_lane!(u32);
+impl_lane!(u64);
+
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The 24 round constants of `f[1600]` (`nr = 24`). Narrower permutations
+/// with `nr` rounds use the *last* `nr` of these, each truncated to the
+/// lane width, per [`Lane::from_round_constant`].
+const RC: [u64; 24] = [
+    1u64,
+    0x8082u64,
+    0x800000000000808au64,
+    0x8000000080008000u64,
+    0x808bu64,
+    0x80000001u64,
+    0x8000000080008081u64,
+    0x8000000000008009u64,
+    0x8au64,
+    0x88u64,
+    0x80008009u64,
+    0x8000000au64,
+    0x8000808bu64,
+    0x800000000000008bu64,
+    0x8000000000008089u64,
+    0x8000000000008003u64,
+    0x8000000000008002u64,
+    0x8000000000000080u64,
+    0x800au64,
+    0x800000008000000au64,
+    0x8000000080008081u64,
+    0x8000000000008080u64,
+    0x80000001u64,
+    0x8000000080008008u64,
+];
+
+/// Applies `rounds` rounds of θ/ρ/π/χ/ι to a 25-lane state of width `T`,
+/// taking ρ's rotation offsets mod `T::BITS` and the round constants from the
+/// last `rounds` entries of [`RC`], truncated to `T::BITS` bits.
+fn keccak_p<T: Lane>(a: &mut [T; 25], rounds: usize) {
+    for &rc in &RC[24 - rounds..] {
+        let mut array = [T::default(); 5];
+
+        // Theta
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                array[x] = array[x] ^ a[x + y];
+            }
+        }
+
+        for x in 0..5 {
+            for y_count in 0..5 {
+                let y = y_count * 5;
+                a[y + x] = a[y + x] ^ array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        for x in 0..24 {
+            array[0] = a[PI[x]];
+            a[PI[x]] = last.rotate_left(RHO[x]);
+            last = array[0];
+        }
+
+        // Chi
+        for y_step in 0..5 {
+            let y = y_step * 5;
+
+            array.copy_from_slice(&a[y..y + 5]);
+
+            for x in 0..5 {
+                a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & array[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        a[0] = a[0] ^ T::from_round_constant(rc);
+    }
+}
+
+/// The `f[1600]` permutation on 64-bit lanes (`nr = 24`), as used by
+/// [`Keccak`], [`Sha3`] and [`Shake`].
+///
+/// [`Keccak`]: crate::Keccak
+/// [`Sha3`]: crate::Sha3
+/// [`Shake`]: crate::Shake
+#[derive(Clone)]
+pub(crate) struct KeccakF;
+
+impl Permutation for KeccakF {
+    type Lane = u64;
+
+    fn execute(buffer: &mut Buffer<u64>) {
+        keccak_p(buffer.words(), 24);
+    }
+}
+
+/// The `f[800]` permutation on 32-bit lanes (`nr = 22`), backing
+/// [`KeccakP800`](crate::KeccakP800).
+#[derive(Clone)]
+pub(crate) struct KeccakFp800;
+
+impl Permutation for KeccakFp800 {
+    type Lane = u32;
+
+    fn execute(buffer: &mut Buffer<u32>) {
+        keccak_p(buffer.words(), 22);
+    }
+}
+
+/// The `f[400]` permutation on 16-bit lanes (`nr = 20`), backing
+/// [`KeccakP400`](crate::KeccakP400).
+#[derive(Clone)]
+pub(crate) struct KeccakFp400;
+
+impl Permutation for KeccakFp400 {
+    type Lane = u16;
+
+    fn execute(buffer: &mut Buffer<u16>) {
+        keccak_p(buffer.words(), 20);
+    }
+}
+
+/// The `f[200]` permutation on 8-bit lanes (`nr = 18`), backing
+/// [`KeccakP200`](crate::KeccakP200) for lightweight/constrained-hash use
+/// cases and small test vectors.
+#[derive(Clone)]
+pub(crate) struct KeccakFp200;
+
+impl Permutation for KeccakFp200 {
+    type Lane = u8;
+
+    fn execute(buffer: &mut Buffer<u8>) {
+        keccak_p(buffer.words(), 18);
+    }
+}
@@ -0,0 +1,28 @@
+//! Benchmarks the `f[1600]` permutation via `Keccak::v256`.
+//!
+//! There's no `Cargo.toml` in this snapshot to register a `[[bench]]` entry
+//! or a `criterion` dev-dependency for, so this file has never actually
+//! been run through `cargo bench` — it's written in the shape it would
+//! take once those exist (`criterion` as a dev-dependency, this file
+//! registered `harness = false`).
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tiny_keccak::{Hasher, Keccak};
+
+fn hash_various_lengths(c: &mut Criterion) {
+    for &len in &[0usize, 32, 136, 1024, 1_000_000] {
+        let input = vec![0x5au8; len];
+        c.bench_function(&format!("keccak256/{len}"), |b| {
+            b.iter(|| {
+                let mut hasher = Keccak::v256();
+                hasher.update(black_box(&input));
+                let mut output = [0u8; 32];
+                hasher.finalize(&mut output);
+                black_box(output)
+            })
+        });
+    }
+}
+
+criterion_group!(benches, hash_various_lengths);
+criterion_main!(benches);
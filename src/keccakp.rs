@@ -0,0 +1,234 @@
+//! Hashers built directly on the narrower `f[200]`/`f[400]`/`f[800]`
+//! permutations, for lightweight/constrained use cases and for driving
+//! small-width Keccak test vectors.
+//!
+//! Unlike [`Keccak`](crate::Keccak)/[`Sha3`](crate::Sha3)/[`Shake`](crate::Shake),
+//! these take `rate`/`delim` directly rather than a standardized security
+//! level in bits: the narrow permutations aren't standardized hash
+//! functions, just smaller instances of the same sponge construction (see
+//! [`Keccak::custom`](crate::Keccak::custom) for the suffix encoding).
+
+use super::keccakf::{KeccakFp200, KeccakFp400, KeccakFp800};
+use super::{Hasher, KeccakState};
+
+/// A sponge hasher over the `f[200]` permutation (8-bit lanes, 18 rounds,
+/// 25-byte state).
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::{Hasher, KeccakP200};
+///
+/// let mut hasher = KeccakP200::new(18, 0x06);
+/// let mut output = [0u8; 7];
+/// hasher.update(b"hello");
+/// hasher.finalize(&mut output);
+/// ```
+#[derive(Clone)]
+pub struct KeccakP200 {
+    state: KeccakState<KeccakFp200>,
+}
+
+impl KeccakP200 {
+    const WIDTH_BYTES: usize = 25;
+
+    /// Creates a new hasher with the given `rate` (in bytes, non-zero and
+    /// less than the 25-byte state width) and domain-separation suffix
+    /// `delim`.
+    pub fn new(rate: usize, delim: u8) -> Self {
+        assert!(
+            rate < Self::WIDTH_BYTES,
+            "rate must be smaller than the permutation width"
+        );
+        KeccakP200 {
+            state: KeccakState::new(rate, delim),
+        }
+    }
+}
+
+impl Hasher for KeccakP200 {
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+/// A sponge hasher over the `f[400]` permutation (16-bit lanes, 20 rounds,
+/// 50-byte state).
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::{Hasher, KeccakP400};
+///
+/// let mut hasher = KeccakP400::new(36, 0x06);
+/// let mut output = [0u8; 14];
+/// hasher.update(b"hello");
+/// hasher.finalize(&mut output);
+/// ```
+#[derive(Clone)]
+pub struct KeccakP400 {
+    state: KeccakState<KeccakFp400>,
+}
+
+impl KeccakP400 {
+    const WIDTH_BYTES: usize = 50;
+
+    /// Creates a new hasher with the given `rate` (in bytes, non-zero and
+    /// less than the 50-byte state width) and domain-separation suffix
+    /// `delim`.
+    pub fn new(rate: usize, delim: u8) -> Self {
+        assert!(
+            rate < Self::WIDTH_BYTES,
+            "rate must be smaller than the permutation width"
+        );
+        KeccakP400 {
+            state: KeccakState::new(rate, delim),
+        }
+    }
+}
+
+impl Hasher for KeccakP400 {
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+/// A sponge hasher over the `f[800]` permutation (32-bit lanes, 22 rounds,
+/// 100-byte state).
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::{Hasher, KeccakP800};
+///
+/// let mut hasher = KeccakP800::new(72, 0x06);
+/// let mut output = [0u8; 28];
+/// hasher.update(b"hello");
+/// hasher.finalize(&mut output);
+/// ```
+#[derive(Clone)]
+pub struct KeccakP800 {
+    state: KeccakState<KeccakFp800>,
+}
+
+impl KeccakP800 {
+    const WIDTH_BYTES: usize = 100;
+
+    /// Creates a new hasher with the given `rate` (in bytes, non-zero and
+    /// less than the 100-byte state width) and domain-separation suffix
+    /// `delim`.
+    pub fn new(rate: usize, delim: u8) -> Self {
+        assert!(
+            rate < Self::WIDTH_BYTES,
+            "rate must be smaller than the permutation width"
+        );
+        KeccakP800 {
+            state: KeccakState::new(rate, delim),
+        }
+    }
+}
+
+impl Hasher for KeccakP800 {
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest<H: Hasher>(mut hasher: H, input: &[u8], output: &mut [u8]) {
+        hasher.update(input);
+        hasher.finalize(output);
+    }
+
+    #[test]
+    fn keccak_p200_is_deterministic_and_input_sensitive() {
+        let mut a = [0u8; 7];
+        let mut b = [0u8; 7];
+        digest(KeccakP200::new(18, 0x06), b"hello", &mut a);
+        digest(KeccakP200::new(18, 0x06), b"hello", &mut b);
+        assert_eq!(a, b);
+
+        let mut c = [0u8; 7];
+        digest(KeccakP200::new(18, 0x06), b"hellp", &mut c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn keccak_p400_is_deterministic_and_input_sensitive() {
+        let mut a = [0u8; 14];
+        let mut b = [0u8; 14];
+        digest(KeccakP400::new(36, 0x06), b"hello", &mut a);
+        digest(KeccakP400::new(36, 0x06), b"hello", &mut b);
+        assert_eq!(a, b);
+
+        let mut c = [0u8; 14];
+        digest(KeccakP400::new(36, 0x06), b"hellp", &mut c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn keccak_p800_is_deterministic_and_input_sensitive() {
+        let mut a = [0u8; 28];
+        let mut b = [0u8; 28];
+        digest(KeccakP800::new(72, 0x06), b"hello", &mut a);
+        digest(KeccakP800::new(72, 0x06), b"hello", &mut b);
+        assert_eq!(a, b);
+
+        let mut c = [0u8; 28];
+        digest(KeccakP800::new(72, 0x06), b"hellp", &mut c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher() {
+        let mut hasher = KeccakP200::new(18, 0x06);
+        hasher.update(b"garbage to be discarded");
+        hasher.reset();
+        hasher.update(b"hello");
+        let mut got = [0u8; 7];
+        hasher.finalize(&mut got);
+
+        let mut want = [0u8; 7];
+        digest(KeccakP200::new(18, 0x06), b"hello", &mut want);
+        assert_eq!(got, want);
+    }
+}
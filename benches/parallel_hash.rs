@@ -0,0 +1,44 @@
+//! Benchmarks `ParallelHashXOF256::finalize_xof_threaded` against the plain
+//! sequential `finalize_xof`, across a few thread counts, to see whether
+//! spreading leaf hashing across OS threads pays for itself at a given
+//! input size and block size.
+//!
+//! With no `Cargo.toml` in this snapshot to add `criterion` as a
+//! dev-dependency or list a `[[bench]]` entry, nobody has run this through
+//! `cargo bench`; it's laid out the way it would need to be once that
+//! manifest exists, not a measured result about whether threading helps.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tiny_keccak::ParallelHashXof256;
+
+const BLOCK_SIZE: usize = 8192;
+const INPUT_LEN: usize = 8 * 1024 * 1024;
+
+fn sequential_vs_threaded(c: &mut Criterion) {
+    let input = vec![0x5au8; INPUT_LEN];
+
+    c.bench_function("parallel_hash/sequential", |b| {
+        b.iter(|| {
+            let mut hasher = ParallelHashXof256::new(BLOCK_SIZE, &[]);
+            hasher.update(black_box(&input));
+            let mut output = [0u8; 32];
+            hasher.finalize_xof().squeeze(&mut output);
+            black_box(output)
+        })
+    });
+
+    for &thread_count in &[2usize, 4, 8] {
+        c.bench_function(&format!("parallel_hash/threaded/{thread_count}"), |b| {
+            b.iter(|| {
+                let mut hasher = ParallelHashXof256::new(BLOCK_SIZE, &[]);
+                hasher.update(black_box(&input));
+                let mut output = [0u8; 32];
+                hasher.finalize_xof_threaded(thread_count).squeeze(&mut output);
+                black_box(output)
+            })
+        });
+    }
+}
+
+criterion_group!(benches, sequential_vs_threaded);
+criterion_main!(benches);
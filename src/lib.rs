@@ -0,0 +1,235 @@
+//! # tiny-keccak
+//!
+//! An implementation of the FIPS-202-defined SHA-3 and SHAKE functions in Rust.
+//!
+//! The `Keccak-f[1600]` permutation and its narrower `f[800]`/`f[400]`/`f[200]`
+//! siblings share one looped round function, generic over the lane type; no
+//! dependencies beyond `core` are required by default.
+
+#![no_std]
+
+mod keccakf;
+mod keccakp;
+#[cfg(feature = "keccak")]
+mod keccak;
+#[cfg(feature = "sha3")]
+mod sha3;
+#[cfg(feature = "shake")]
+mod shake;
+
+#[cfg(feature = "keccak")]
+pub use crate::keccak::Keccak;
+#[cfg(feature = "sha3")]
+pub use crate::sha3::Sha3;
+#[cfg(feature = "shake")]
+pub use crate::shake::Shake;
+
+pub use crate::keccakp::{KeccakP200, KeccakP400, KeccakP800};
+
+use crate::keccakf::Lane;
+
+/// A trait for hashing an arbitrary stream of bytes.
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::{Hasher, Keccak};
+///
+/// let mut hasher = Keccak::v256();
+/// let mut output = [0u8; 32];
+/// hasher.update(b"hello world");
+/// hasher.finalize(&mut output);
+/// ```
+pub trait Hasher {
+    /// Absorb additional input. Can be called multiple times.
+    fn update(&mut self, input: &[u8]);
+
+    /// Pad and squeeze the state to the output.
+    fn finalize(self, output: &mut [u8]);
+
+    /// Zeroes the sponge buffer and resets the absorb offset, preserving the
+    /// configured rate and domain-separation byte, so the hasher can absorb
+    /// a new, independent message without reallocating.
+    fn reset(&mut self);
+
+    /// Squeezes into `output` and then [`reset`](Hasher::reset)s in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]);
+}
+
+/// Converts a security level in bits to a sponge rate in bytes, following the
+/// `rate = 200 - capacity` relationship used throughout the Keccak family
+/// (`capacity = 2 * bits / 8`).
+fn bits_to_rate(bits: usize) -> usize {
+    200 - bits / 4
+}
+
+/// The 25-lane sponge buffer shared by every member of the Keccak family.
+/// Its byte size is `25 * size_of::<T>()`: 200 bytes for the `f[1600]`
+/// permutation's 64-bit lanes, down to 25 bytes for `f[200]`'s 8-bit lanes.
+#[derive(Clone)]
+struct Buffer<T: Lane>([T; 25]);
+
+impl<T: Lane> Default for Buffer<T> {
+    fn default() -> Self {
+        Buffer([T::default(); 25])
+    }
+}
+
+impl<T: Lane> Buffer<T> {
+    fn words(&mut self) -> &mut [T; 25] {
+        &mut self.0
+    }
+
+    #[cfg(target_endian = "little")]
+    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
+        let bytes = core::mem::size_of::<T>() * 25;
+        let ptr = self.0.as_mut_ptr() as *mut u8;
+        let buffer = unsafe { core::slice::from_raw_parts_mut(ptr, bytes) };
+        f(&mut buffer[offset..][..len]);
+    }
+
+    #[cfg(target_endian = "big")]
+    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
+        fn swap_endianess<T: Lane>(buffer: &mut [T; 25]) {
+            for item in buffer {
+                *item = item.swap_bytes();
+            }
+        }
+
+        swap_endianess(&mut self.0);
+        let bytes = core::mem::size_of::<T>() * 25;
+        let ptr = self.0.as_mut_ptr() as *mut u8;
+        let buffer = unsafe { core::slice::from_raw_parts_mut(ptr, bytes) };
+        f(&mut buffer[offset..][..len]);
+        swap_endianess(&mut self.0);
+    }
+
+    fn setout(&mut self, dst: &mut [u8], offset: usize, len: usize) {
+        self.execute(offset, len, |buffer| dst[..len].copy_from_slice(buffer));
+    }
+
+    fn xorin(&mut self, src: &[u8], offset: usize, len: usize) {
+        self.execute(offset, len, |dst| {
+            assert!(dst.len() <= src.len());
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d ^= *s;
+            }
+        });
+    }
+
+    fn pad(&mut self, offset: usize, delim: u8, rate: usize) {
+        self.execute(offset, 1, |buff| buff[0] ^= delim);
+        self.execute(rate - 1, 1, |buff| buff[0] ^= 0x80);
+    }
+}
+
+/// A sponge permutation, parameterized so that different Keccak-family
+/// members (`f[1600]`, `f[800]`, `f[400]`, `f[200]`, ...) can share the
+/// [`KeccakState`] plumbing.
+trait Permutation {
+    /// The unsigned lane width this permutation operates on.
+    type Lane: Lane;
+
+    fn execute(a: &mut Buffer<Self::Lane>);
+}
+
+/// The generic sponge state (absorb buffer + rate/offset/domain-separation
+/// bookkeeping) shared by [`Keccak`], [`Sha3`] and [`Shake`].
+#[derive(Clone)]
+struct KeccakState<P: Permutation> {
+    buffer: Buffer<P::Lane>,
+    offset: usize,
+    rate: usize,
+    delim: u8,
+    permutation: core::marker::PhantomData<P>,
+}
+
+impl<P: Permutation> KeccakState<P> {
+    /// Creates a sponge state with the given `rate` (in bytes) and
+    /// domain-separation suffix `delim`. `delim`'s bits, read from the LSB,
+    /// are the suffix bits appended after the message followed by the first
+    /// `1` bit of `pad10*1`; the rule's terminating `1` bit is added
+    /// separately by `finalize`/`pad`.
+    /// `Keccak::custom` exposes this on the public `Keccak` API.
+    fn new(rate: usize, delim: u8) -> Self {
+        assert!(rate != 0, "rate cannot be equal 0");
+        KeccakState {
+            buffer: Buffer::default(),
+            offset: 0,
+            rate,
+            delim,
+            permutation: core::marker::PhantomData,
+        }
+    }
+
+    fn keccak(&mut self) {
+        P::execute(&mut self.buffer);
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        let mut input = input;
+        let rate = self.rate;
+        if self.offset != 0 {
+            let head_len = rate - self.offset;
+            let head_len = core::cmp::min(head_len, input.len());
+            self.buffer.xorin(&input[..head_len], self.offset, head_len);
+            self.offset += head_len;
+            input = &input[head_len..];
+            if self.offset != rate {
+                return;
+            }
+            self.keccak();
+            self.offset = 0;
+        }
+
+        while input.len() >= rate {
+            self.buffer.xorin(&input[..rate], 0, rate);
+            self.keccak();
+            input = &input[rate..];
+        }
+
+        if !input.is_empty() {
+            self.buffer.xorin(input, 0, input.len());
+            self.offset = input.len();
+        }
+    }
+
+    fn pad(&mut self) {
+        self.buffer.pad(self.offset, self.delim, self.rate);
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        let rate = self.rate;
+        let mut output = output;
+
+        while output.len() >= rate {
+            self.buffer.setout(&mut output[..rate], 0, rate);
+            self.keccak();
+            output = &mut output[rate..];
+        }
+
+        let len = output.len();
+        self.buffer.setout(output, 0, len);
+    }
+
+    fn finalize(mut self, output: &mut [u8]) {
+        self.pad();
+        self.keccak();
+        self.squeeze(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, preserving
+    /// `rate` and `delim`, so the state can be reused for a fresh message.
+    fn reset(&mut self) {
+        self.buffer = Buffer::default();
+        self.offset = 0;
+    }
+
+    /// Squeezes into `output` and then resets, without consuming `self`.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.pad();
+        self.keccak();
+        self.squeeze(output);
+        self.reset();
+    }
+}
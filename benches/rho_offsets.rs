@@ -0,0 +1,32 @@
+//! Benchmarks the table-driven ρ/π step (`keccak_p_round`, the default) against
+//! the literal-constant-rotation variant (`keccak_p_round_unrolled`, enabled via
+//! the `rho-unrolled` feature) by rerunning the same public-API benchmark
+//! with and without the feature flag, since both round functions are
+//! `pub(crate)` and not reachable directly from an external bench crate.
+//!
+//! No `Cargo.toml` exists in this snapshot to wire a `[[bench]]` entry or
+//! `criterion` dev-dependency into, so this comparison has never actually
+//! been run — the two variants are only known to produce identical output
+//! (see `rounds_tests::unrolled_rho_pi_matches_the_table_driven_version`),
+//! not to differ in speed.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tiny_keccak::{Hasher, Keccak};
+
+fn hash_various_lengths(c: &mut Criterion) {
+    for &len in &[0usize, 32, 136, 1024, 1_000_000] {
+        let input = vec![0x5au8; len];
+        c.bench_function(&format!("keccak256/{len}"), |b| {
+            b.iter(|| {
+                let mut hasher = Keccak::v256();
+                hasher.update(black_box(&input));
+                let mut output = [0u8; 32];
+                hasher.finalize(&mut output);
+                black_box(output)
+            })
+        });
+    }
+}
+
+criterion_group!(benches, hash_various_lengths);
+criterion_main!(benches);
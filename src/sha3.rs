@@ -0,0 +1,269 @@
+//! The `SHA3` hash functions.
+
+use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+
+/// The `SHA3` hash functions defined in [`FIPS-202`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["sha3"] }
+/// ```
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+#[derive(Clone)]
+pub struct Sha3 {
+    state: KeccakState<KeccakF>,
+    output_bytes: usize,
+}
+
+impl Sha3 {
+    const DELIM: u8 = 0x06;
+
+    /// Creates  new [`Sha3`] hasher with a security level of 224 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v224() -> Sha3 {
+        Sha3::new(224)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 256 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v256() -> Sha3 {
+        Sha3::new(256)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 384 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v384() -> Sha3 {
+        Sha3::new(384)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 512 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v512() -> Sha3 {
+        Sha3::new(512)
+    }
+
+    fn new(bits: usize) -> Sha3 {
+        Sha3 {
+            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+            output_bytes: bits / 8,
+        }
+    }
+
+    /// Pads, squeezes and returns the digest as a fixed-size array, checking
+    /// `N` against the security level this hasher was constructed with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not match the output length implied by the
+    /// `vNNN()` constructor used to create this hasher.
+    pub fn finalize_array<const N: usize>(self) -> [u8; N] {
+        assert_eq!(
+            N, self.output_bytes,
+            "output array length does not match the configured security level",
+        );
+        let mut output = [0u8; N];
+        self.finalize(&mut output);
+        output
+    }
+
+    /// Computes the SHA3-224 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v224()` followed by `update` and `finalize`.
+    pub fn sha3_224(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v224();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-256 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v256()` followed by `update` and `finalize`.
+    pub fn sha3_256(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v256();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-384 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v384()` followed by `update` and `finalize`.
+    pub fn sha3_384(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v384();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-512 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v512()` followed by `update` and `finalize`.
+    pub fn sha3_512(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v512();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+}
+
+impl Hasher for Sha3 {
+    /// Absorb additional input. Can be called multiple times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// #
+    /// # fn main() {
+    /// # let mut sha3 = Sha3::v256();
+    /// sha3.update(b"hello");
+    /// sha3.update(b" world");
+    /// # }
+    /// ```
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Pad and squeeze the state to the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// #
+    /// # fn main() {
+    /// # let sha3 = Sha3::v256();
+    /// # let mut output = [0u8; 32];
+    /// sha3.finalize(&mut output);
+    /// # }
+    /// #
+    /// ```
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Sha3`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known vector: FIPS-202 SHA3-256("").
+    #[test]
+    fn sha3_256_of_empty_input_matches_known_vector() {
+        let mut sha3 = Sha3::v256();
+        let mut output = [0u8; 32];
+        sha3.update(b"");
+        sha3.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61,
+                0xd6, 0x62, 0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b,
+                0x80, 0xf8, 0x43, 0x4a,
+            ]
+        );
+    }
+
+    #[test]
+    fn sha3_256_of_hello_matches_known_vector() {
+        let mut sha3 = Sha3::v256();
+        let mut output = [0u8; 32];
+        sha3.update(b"hello");
+        sha3.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x33, 0x38, 0xbe, 0x69, 0x4f, 0x50, 0xc5, 0xf3, 0x38, 0x81, 0x49, 0x86, 0xcd, 0xf0,
+                0x68, 0x64, 0x53, 0xa8, 0x88, 0xb8, 0x4f, 0x42, 0x4d, 0x79, 0x2a, 0xf4, 0xb9, 0x20,
+                0x23, 0x98, 0xf3, 0x92,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-224("hello").
+    #[test]
+    fn sha3_224_one_shot_matches_known_vector() {
+        let mut output = [0u8; 28];
+        Sha3::sha3_224(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0xb8, 0x7f, 0x88, 0xc7, 0x27, 0x02, 0xff, 0xf1, 0x74, 0x8e, 0x58, 0xb8, 0x7e, 0x91,
+                0x41, 0xa4, 0x2c, 0x0d, 0xbe, 0xdc, 0x29, 0xa7, 0x8c, 0xb0, 0xd4, 0xa5, 0xcd, 0x81,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-384("hello").
+    #[test]
+    fn sha3_384_one_shot_matches_known_vector() {
+        let mut output = [0u8; 48];
+        Sha3::sha3_384(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x72, 0x0a, 0xea, 0x11, 0x01, 0x9e, 0xf0, 0x64, 0x40, 0xfb, 0xf0, 0x5d, 0x87, 0xaa,
+                0x24, 0x68, 0x0a, 0x21, 0x53, 0xdf, 0x39, 0x07, 0xb2, 0x36, 0x31, 0xe7, 0x17, 0x7c,
+                0xe6, 0x20, 0xfa, 0x13, 0x30, 0xff, 0x07, 0xc0, 0xfd, 0xde, 0xe5, 0x46, 0x99, 0xa4,
+                0xc3, 0xee, 0x0e, 0xe9, 0xd8, 0x87,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-512("hello").
+    #[test]
+    fn sha3_512_one_shot_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Sha3::sha3_512(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x75, 0xd5, 0x27, 0xc3, 0x68, 0xf2, 0xef, 0xe8, 0x48, 0xec, 0xf6, 0xb0, 0x73, 0xa3,
+                0x67, 0x67, 0x80, 0x08, 0x05, 0xe9, 0xee, 0xf2, 0xb1, 0x85, 0x7d, 0x5f, 0x98, 0x4f,
+                0x03, 0x6e, 0xb6, 0xdf, 0x89, 0x1d, 0x75, 0xf7, 0x2d, 0x9b, 0x15, 0x45, 0x18, 0xc1,
+                0xcd, 0x58, 0x83, 0x52, 0x86, 0xd1, 0xda, 0x9a, 0x38, 0xde, 0xba, 0x3d, 0xe9, 0x8b,
+                0x5a, 0x53, 0xe5, 0xed, 0x78, 0xa8, 0x49, 0x76,
+            ]
+        );
+    }
+
+    #[test]
+    fn finalize_array_matches_finalize() {
+        let mut via_finalize = [0u8; 32];
+        Sha3::sha3_256(b"hello", &mut via_finalize);
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"hello");
+        let via_array: [u8; 32] = sha3.finalize_array();
+        assert_eq!(via_array, via_finalize);
+    }
+
+    #[test]
+    #[should_panic(expected = "output array length does not match the configured security level")]
+    fn finalize_array_panics_on_mismatched_length() {
+        let _: [u8; 16] = Sha3::v256().finalize_array();
+    }
+}
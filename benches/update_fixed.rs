@@ -0,0 +1,53 @@
+//! Benchmarks `Keccak::update_fixed` against plain `Keccak::update` for a
+//! struct-hashing workload: repeatedly absorbing a fixed-size 64-byte
+//! struct, where `update_fixed`'s compile-time-known length should let the
+//! optimizer elide the general absorb loop's bounds checks.
+//!
+//! This snapshot has no `Cargo.toml` to register a `[[bench]]` entry or
+//! `criterion` dev-dependency in, so `cargo bench` has never actually run
+//! this file — it's shaped for the `Cargo.toml` a real checkout would add
+//! (`criterion` as a dev-dependency, `harness = false`), not proven fast.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use tiny_keccak::{Hasher, Keccak};
+
+const UPDATE_COUNT: usize = 1_000_000;
+
+struct Struct64 {
+    bytes: [u8; 64],
+}
+
+fn update_slice(c: &mut Criterion) {
+    let s = Struct64 { bytes: [0x5au8; 64] };
+
+    c.bench_function("keccak256/1M x 64-byte update (slice)", |b| {
+        b.iter(|| {
+            let mut hasher = Keccak::v256();
+            for _ in 0..UPDATE_COUNT {
+                hasher.update(black_box(&s.bytes[..]));
+            }
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            black_box(output)
+        })
+    });
+}
+
+fn update_fixed(c: &mut Criterion) {
+    let s = Struct64 { bytes: [0x5au8; 64] };
+
+    c.bench_function("keccak256/1M x 64-byte update_fixed", |b| {
+        b.iter(|| {
+            let mut hasher = Keccak::v256();
+            for _ in 0..UPDATE_COUNT {
+                hasher.update_fixed(black_box(&s.bytes));
+            }
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            black_box(output)
+        })
+    });
+}
+
+criterion_group!(benches, update_slice, update_fixed);
+criterion_main!(benches);
@@ -0,0 +1,147 @@
+//! The `SHAKE` extendable-output functions.
+
+use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+
+/// The `SHAKE` extendable-output functions defined in [`FIPS-202`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["shake"] }
+/// ```
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+#[derive(Clone)]
+pub struct Shake {
+    state: KeccakState<KeccakF>,
+}
+
+impl Shake {
+    const DELIM: u8 = 0x1f;
+
+    /// Creates  new [`Shake`] hasher with a security level of 128 bits.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v128() -> Shake {
+        Shake::new(128)
+    }
+
+    /// Creates  new [`Shake`] hasher with a security level of 256 bits.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v256() -> Shake {
+        Shake::new(256)
+    }
+
+    fn new(bits: usize) -> Shake {
+        Shake {
+            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+        }
+    }
+}
+
+impl Hasher for Shake {
+    /// Absorb additional input. Can be called multiple times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Shake};
+    /// #
+    /// # fn main() {
+    /// # let mut shake = Shake::v256();
+    /// shake.update(b"hello");
+    /// shake.update(b" world");
+    /// # }
+    /// ```
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Pad and squeeze the state to the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Shake};
+    /// #
+    /// # fn main() {
+    /// # let shake = Shake::v256();
+    /// # let mut output = [0u8; 32];
+    /// shake.finalize(&mut output);
+    /// # }
+    /// #
+    /// ```
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Shake`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known vector: FIPS-202 SHAKE128(""), first 32 output bytes.
+    #[test]
+    fn shake128_of_empty_input_matches_known_vector() {
+        let mut shake = Shake::v128();
+        let mut output = [0u8; 32];
+        shake.update(b"");
+        shake.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x7f, 0x9c, 0x2b, 0xa4, 0xe8, 0x8f, 0x82, 0x7d, 0x61, 0x60, 0x45, 0x50, 0x76, 0x05,
+                0x85, 0x3e, 0xd7, 0x3b, 0x80, 0x93, 0xf6, 0xef, 0xbc, 0x88, 0xeb, 0x1a, 0x6e, 0xac,
+                0xfa, 0x66, 0xef, 0x26,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHAKE256(""), first 32 output bytes.
+    #[test]
+    fn shake256_of_empty_input_matches_known_vector() {
+        let mut shake = Shake::v256();
+        let mut output = [0u8; 32];
+        shake.update(b"");
+        shake.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x46, 0xb9, 0xdd, 0x2b, 0x0b, 0xa8, 0x8d, 0x13, 0x23, 0x3b, 0x3f, 0xeb, 0x74, 0x3e,
+                0xeb, 0x24, 0x3f, 0xcd, 0x52, 0xea, 0x62, 0xb8, 0x1b, 0x82, 0xb5, 0x0c, 0x27, 0x64,
+                0x6e, 0xd5, 0x76, 0x2f,
+            ]
+        );
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher() {
+        let mut hasher = Shake::v128();
+        hasher.update(b"garbage to discard");
+        hasher.reset();
+        hasher.update(b"");
+        let mut got = [0u8; 32];
+        hasher.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        Shake::v128().finalize(&mut want);
+        assert_eq!(got, want);
+    }
+}
@@ -0,0 +1,83 @@
+//! [`Commitment`]: a Keccak-256-based commit/verify helper for the common
+//! `commit = H(domain || randomness || message)` construction, so callers
+//! don't each reimplement the length-encoded framing that keeps `domain`,
+//! `randomness` and `message` from colliding across their boundaries.
+
+use crate::sp800::encode_string;
+use crate::{ct_eq, Hasher, Keccak};
+
+/// A namespace for [`commit`](Self::commit)/[`verify`](Self::verify); holds
+/// no state of its own; every commitment is independent, keyed only by its
+/// `domain`, `randomness` and `message` inputs.
+pub struct Commitment;
+
+impl Commitment {
+    /// Computes `commit = Keccak256(encode_string(domain) ||
+    /// encode_string(randomness) || encode_string(message))`.
+    ///
+    /// Each of the three inputs is [`encode_string`]d (NIST SP800-185's
+    /// length-prefixed framing) before being absorbed, rather than
+    /// concatenated directly, so `commit(b"d", b"", b"xy")` and
+    /// `commit(b"d", b"x", b"y")` can never collide: without the length
+    /// prefixes, both would absorb `d` followed by `xy`, silently letting a
+    /// byte shift between `randomness` and `message` open the same
+    /// commitment to two different pairs.
+    pub fn commit(domain: &[u8], randomness: &[u8], message: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        hasher.update(&encode_string(domain));
+        hasher.update(&encode_string(randomness));
+        hasher.update(&encode_string(message));
+        hasher.finalize_array()
+    }
+
+    /// Recomputes [`commit`](Self::commit) from the claimed opening and
+    /// compares it against `commitment` in constant time via [`ct_eq`], so
+    /// checking a commitment doesn't leak timing information about which
+    /// byte of a tampered opening first diverges.
+    pub fn verify(commitment: &[u8; 32], domain: &[u8], randomness: &[u8], message: &[u8]) -> bool {
+        ct_eq(&Self::commit(domain, randomness, message), commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifting_a_byte_between_randomness_and_message_changes_the_commitment() {
+        let a = Commitment::commit(b"domain", b"", b"xy");
+        let b = Commitment::commit(b"domain", b"x", b"y");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn verify_accepts_the_correct_opening() {
+        let commitment = Commitment::commit(b"domain", b"randomness", b"message");
+        assert!(Commitment::verify(&commitment, b"domain", b"randomness", b"message"));
+    }
+
+    #[test]
+    fn verify_rejects_a_tampered_message() {
+        let commitment = Commitment::commit(b"domain", b"randomness", b"message");
+        assert!(!Commitment::verify(&commitment, b"domain", b"randomness", b"tampered"));
+    }
+
+    #[test]
+    fn verify_rejects_a_tampered_randomness() {
+        let commitment = Commitment::commit(b"domain", b"randomness", b"message");
+        assert!(!Commitment::verify(&commitment, b"domain", b"wrong-random", b"message"));
+    }
+
+    #[test]
+    fn verify_rejects_a_tampered_domain() {
+        let commitment = Commitment::commit(b"domain", b"randomness", b"message");
+        assert!(!Commitment::verify(&commitment, b"other-domain", b"randomness", b"message"));
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let a = Commitment::commit(b"domain", b"randomness", b"message");
+        let b = Commitment::commit(b"domain", b"randomness", b"message");
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,82 @@
+//! An adapter bridging [`Keccak`] to [`core::hash::Hasher`], for plugging
+//! into hashing-aware collections (e.g. `std::collections::HashMap` via
+//! `BuildHasherDefault`) that expect the standard library's hasher trait
+//! rather than this crate's own [`Hasher`].
+//!
+//! This is explicitly not a cryptographic use of the collection: nothing
+//! about `core::hash::Hasher`'s contract (a single `u64` output, no domain
+//! separation between calls) preserves Keccak's security properties. It is
+//! only a convenient bridge for users who want a `HashMap` keyed by
+//! Keccak's output distribution without pulling in another hashing crate.
+
+use crate::{Hasher as _, Keccak};
+
+/// Adapts [`Keccak`] to [`core::hash::Hasher`].
+///
+/// [`finish`](core::hash::Hasher::finish) finalizes a *clone* of the
+/// current sponge state, so it does not consume `self` and may be called
+/// repeatedly, per `core::hash::Hasher`'s contract.
+#[derive(Clone)]
+pub struct KeccakHasher(Keccak);
+
+impl KeccakHasher {
+    /// Creates a new adapter around a fresh 256-bit [`Keccak`] hasher.
+    pub fn new() -> Self {
+        KeccakHasher(Keccak::v256())
+    }
+}
+
+impl Default for KeccakHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::hash::Hasher for KeccakHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut output = [0u8; 8];
+        self.0.clone().finalize(&mut output);
+        u64::from_le_bytes(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::hash::Hasher as _;
+
+    #[test]
+    fn finish_does_not_consume_and_is_idempotent() {
+        let mut hasher = KeccakHasher::new();
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), hasher.finish());
+    }
+
+    #[test]
+    fn different_input_hashes_differently() {
+        let mut a = KeccakHasher::new();
+        a.write(b"hello");
+
+        let mut b = KeccakHasher::new();
+        b.write(b"world");
+
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn works_as_a_hashmap_build_hasher_default() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasherDefault;
+
+        let mut map: HashMap<&str, i32, BuildHasherDefault<KeccakHasher>> = HashMap::default();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        assert_eq!(map.get("a"), Some(&1));
+        assert_eq!(map.get("b"), Some(&2));
+    }
+}
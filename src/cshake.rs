@@ -0,0 +1,340 @@
+//! `cSHAKE`: the customizable SHAKE function from NIST SP800-185, the base
+//! construction [`KMAC`](crate::Kmac128) and friends build on top of.
+//!
+//! Unlike plain SHAKE, cSHAKE takes a function-name string `N` (reserved
+//! for NIST-defined derived functions like `KMAC`; callers should pass
+//! `&[]` for a bespoke function) and a customization string `S`, and mixes
+//! both in via [`sp800::bytepad`]/[`sp800::encode_string`] before absorbing
+//! the message. When both `N` and `S` are empty, cSHAKE is defined to
+//! degrade to plain SHAKE, which this reproduces by using SHAKE's `0x1f`
+//! domain separator and skipping the encoded prefix entirely in that case.
+
+use crate::sp800::{encode_string, left_encode};
+use crate::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState, XofReader};
+
+/// `bytepad(input, w)`: `left_encode(w)` followed by `input`, then zero
+/// bytes until the total length is a multiple of `w`.
+fn bytepad(input: &[u8], w: usize) -> std::vec::Vec<u8> {
+    let mut encoded_w = [0u8; 9];
+    let encoded_w = left_encode(w as u64, &mut encoded_w);
+    let mut out = std::vec::Vec::with_capacity(encoded_w.len() + input.len() + w);
+    out.extend_from_slice(encoded_w);
+    out.extend_from_slice(input);
+    let padding = (w - out.len() % w) % w;
+    out.resize(out.len() + padding, 0);
+    out
+}
+
+/// The `0x04` domain-separation suffix used whenever cSHAKE doesn't degrade
+/// to plain SHAKE (i.e. `N` and/or `S` is non-empty).
+const CSHAKE_DELIM: u8 = 0x04;
+
+/// Plain SHAKE's domain-separation suffix, reused when cSHAKE degrades to
+/// it (`N` and `S` both empty).
+const SHAKE_DELIM: u8 = 0x1f;
+
+macro_rules! cshake {
+    ($name:ident, $reader:ident, $builder:ident, $doc:expr, $bits:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            state: KeccakState<KeccakF>,
+        }
+
+        impl $name {
+            /// Creates a new hasher with function-name string `n` and
+            /// customization string `s`. Pass `&[]` for both to get plain
+            /// SHAKE's behavior (cSHAKE is defined to degrade to it).
+            pub fn new(n: &[u8], s: &[u8]) -> Self {
+                let rate = bits_to_rate($bits);
+                if n.is_empty() && s.is_empty() {
+                    return $name {
+                        state: KeccakState::new(rate, SHAKE_DELIM),
+                    };
+                }
+                let mut state = KeccakState::new(rate, CSHAKE_DELIM);
+                let mut prefix = encode_string(n);
+                prefix.extend_from_slice(&encode_string(s));
+                state.update(&bytepad(&prefix, rate));
+                $name { state }
+            }
+
+            /// Pads the absorbed input and returns a reader that squeezes
+            /// output in a sequence of calls instead of one fixed-size
+            /// buffer.
+            #[doc(alias = "into_xof")]
+            pub fn finalize_xof(self) -> $reader {
+                $reader(XofReader::new(self.state))
+            }
+
+            #[cfg(feature = "alloc")]
+            #[doc = concat!(
+                "Pads the absorbed input and squeezes `len` bytes into a \
+                 heap-allocated boxed slice, for callers (e.g. using [`",
+                stringify!($name),
+                "`] as a KDF) that only learn the desired output length at \
+                 runtime and would otherwise need to pre-allocate a buffer \
+                 themselves before calling `finalize_xof`.",
+            )]
+            pub fn finalize_boxed(self, len: usize) -> alloc::boxed::Box<[u8]> {
+                let mut reader = self.finalize_xof();
+                let mut output = alloc::vec![0u8; len].into_boxed_slice();
+                reader.squeeze(&mut output);
+                output
+            }
+
+            #[doc = concat!(
+                "Starts a [`", stringify!($builder), "`] with function-name \
+                 string `n`, for streaming a large customization string `S` \
+                 in pieces via [`update_customization`](", stringify!($builder), "::update_customization) \
+                 instead of assembling it into one slice up front like \
+                 [`new`](Self::new) requires.",
+            )]
+            pub fn builder(n: &[u8]) -> $builder {
+                $builder {
+                    n: n.to_vec(),
+                    s: std::vec::Vec::new(),
+                }
+            }
+        }
+
+        #[doc = concat!(
+            "Two-phase builder for [`", stringify!($name), "`], returned by \
+             [`", stringify!($name), "::builder`], that lets the \
+             customization string `S` be streamed in pieces via \
+             [`update_customization`](Self::update_customization) before \
+             [`build`](Self::build) computes the `left_encode` length \
+             prefix and absorbs it.",
+        )]
+        pub struct $builder {
+            n: std::vec::Vec<u8>,
+            s: std::vec::Vec<u8>,
+        }
+
+        impl $builder {
+            /// Appends `s` to the accumulated customization string. Can be
+            /// called multiple times; the pieces are concatenated in call
+            /// order, exactly as if the full concatenation had been passed
+            /// to [`new`](Self::build) as one slice.
+            pub fn update_customization(&mut self, s: &[u8]) -> &mut Self {
+                self.s.extend_from_slice(s);
+                self
+            }
+
+            #[doc = concat!(
+                "Emits the `left_encode`-prefixed function-name and \
+                 (now-complete) customization strings, absorbs them, and \
+                 returns the [`", stringify!($name), "`] hasher ready for \
+                 [`update`](crate::Hasher::update).",
+            )]
+            pub fn build(self) -> $name {
+                $name::new(&self.n, &self.s)
+            }
+        }
+
+        impl Hasher for $name {
+            fn update(&mut self, input: &[u8]) {
+                self.state.update(input);
+            }
+
+            fn finalize(self, output: &mut [u8]) {
+                self.state.finalize(output);
+            }
+
+            fn reset(&mut self) {
+                self.state.reset();
+            }
+
+            fn finalize_reset(&mut self, output: &mut [u8]) {
+                self.state.finalize_reset(output);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        crate::impl_io_write!($name);
+        crate::impl_fmt_write!($name);
+        crate::impl_debug_via_state!($name);
+        crate::impl_xof!($name, $reader);
+
+        #[doc = concat!("An extendable-output reader returned by [`", stringify!($name), "::finalize_xof`].")]
+        #[derive(Clone)]
+        pub struct $reader(XofReader<KeccakF>);
+
+        impl $reader {
+            /// Squeezes `buf.len()` more bytes, continuing from wherever the
+            /// previous `squeeze` call (if any) left off.
+            pub fn squeeze(&mut self, buf: &mut [u8]) {
+                self.0.squeeze(buf);
+            }
+        }
+    };
+}
+
+cshake!(
+    CShake128,
+    CShake128Reader,
+    CShake128Builder,
+    "`cSHAKE128`: the 128-bit-security customizable SHAKE function.",
+    128
+);
+cshake!(
+    CShake256,
+    CShake256Reader,
+    CShake256Builder,
+    "`cSHAKE256`: the 256-bit-security customizable SHAKE function.",
+    256
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_name_and_customization_degrades_to_plain_shake() {
+        let mut cshake = CShake128::new(&[], &[]);
+        cshake.update(b"hello");
+        let mut cshake_out = [0u8; 32];
+        cshake.finalize(&mut cshake_out);
+
+        #[cfg(feature = "shake")]
+        {
+            let mut shake = crate::Shake::v128();
+            shake.update(b"hello");
+            let mut shake_out = [0u8; 32];
+            shake.finalize(&mut shake_out);
+            assert_eq!(cshake_out, shake_out);
+        }
+    }
+
+    #[test]
+    fn empty_message_with_empty_name_and_customization_matches_plain_shake_of_empty_input() {
+        // Same degeneration as `empty_name_and_customization_degrades_to_plain_shake`,
+        // but with zero absorbed message bytes too, so this only passes if
+        // finalizing with nothing absorbed still applies exactly one
+        // padding permutation instead of, say, skipping it as a shortcut
+        // for "there's nothing to hash".
+        let cshake = CShake128::new(&[], &[]);
+        let mut cshake_out = [0u8; 32];
+        cshake.finalize(&mut cshake_out);
+
+        #[cfg(feature = "shake")]
+        {
+            let shake = crate::Shake::v128();
+            let mut shake_out = [0u8; 32];
+            shake.finalize(&mut shake_out);
+            assert_eq!(cshake_out, shake_out);
+        }
+        assert_ne!(cshake_out, [0u8; 32]);
+    }
+
+    #[test]
+    fn a_non_empty_customization_diverges_from_plain_shake() {
+        let mut cshake = CShake128::new(&[], b"my customization");
+        cshake.update(b"hello");
+        let mut cshake_out = [0u8; 32];
+        cshake.finalize(&mut cshake_out);
+
+        let mut plain = CShake128::new(&[], &[]);
+        plain.update(b"hello");
+        let mut plain_out = [0u8; 32];
+        plain.finalize(&mut plain_out);
+
+        assert_ne!(cshake_out, plain_out);
+    }
+
+    #[test]
+    fn different_function_names_diverge() {
+        let mut a = CShake256::new(b"FnA", &[]);
+        let mut b = CShake256::new(b"FnB", &[]);
+        a.update(b"hello");
+        b.update(b"hello");
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn xof_reader_matches_single_shot() {
+        let mut single_shot = CShake256::new(b"N", b"S");
+        single_shot.update(b"hello");
+        let mut want = [0u8; 300];
+        single_shot.finalize(&mut want);
+
+        let mut streamed = CShake256::new(b"N", b"S");
+        streamed.update(b"hello");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..17]);
+        reader.squeeze(&mut got[17..]);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn builder_streaming_customization_matches_passing_it_as_one_slice() {
+        let mut streamed = CShake256::builder(b"my-function");
+        streamed.update_customization(b"first ");
+        streamed.update_customization(b"second ");
+        streamed.update_customization(b"third");
+        let mut streamed = streamed.build();
+        streamed.update(b"hello");
+        let mut streamed_out = [0u8; 32];
+        streamed.finalize(&mut streamed_out);
+
+        let mut direct = CShake256::new(b"my-function", b"first second third");
+        direct.update(b"hello");
+        let mut direct_out = [0u8; 32];
+        direct.finalize(&mut direct_out);
+
+        assert_eq!(streamed_out, direct_out);
+    }
+
+    // `encode_string` switches from a 1-byte to a multi-byte `left_encode`d
+    // bit-length prefix once the string is 256 bytes or longer; exercise
+    // that path through the full construction (not just `encode_string` in
+    // isolation, already covered in `sp800.rs`) to make sure a long
+    // customization string is absorbed correctly rather than silently
+    // truncated or corrupted.
+    #[test]
+    fn a_customization_string_longer_than_255_bytes_is_absorbed_correctly() {
+        let long_s = [0x42u8; 300];
+
+        let mut a = CShake128::new(&[], &long_s);
+        a.update(b"hello");
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let mut b = CShake128::new(&[], &long_s);
+        b.update(b"hello");
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+        assert_eq!(out_a, out_b, "same long customization must be deterministic");
+
+        let mut truncated_s = long_s;
+        truncated_s[299] ^= 0xff;
+        let mut c = CShake128::new(&[], &truncated_s);
+        c.update(b"hello");
+        let mut out_c = [0u8; 32];
+        c.finalize(&mut out_c);
+        assert_ne!(out_a, out_c, "a single differing byte must change the digest");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn finalize_boxed_matches_a_chunked_squeeze() {
+        let mut streamed = CShake128::new(b"N", b"S");
+        streamed.update(b"hello");
+        let mut want = [0u8; 100];
+        let mut reader = streamed.finalize_xof();
+        reader.squeeze(&mut want[..40]);
+        reader.squeeze(&mut want[40..]);
+
+        let mut boxed = CShake128::new(b"N", b"S");
+        boxed.update(b"hello");
+        let got = boxed.finalize_boxed(100);
+
+        assert_eq!(&*got, &want[..]);
+    }
+}
@@ -0,0 +1,54 @@
+//! Constant-time comparison, for verifying MAC tags ([`Kmac128`](crate::Kmac128)/
+//! [`Kmac256`](crate::Kmac256) and friends) without leaking timing
+//! information through an early-exit `==`.
+
+/// Compares `a` and `b` in constant time, returning whether they have equal
+/// length and equal contents.
+///
+/// Unlike `a == b`, this never short-circuits on the first differing byte:
+/// every byte of the shorter length is OR-accumulated into a single
+/// difference marker, and the two are only reported unequal once, at the
+/// end. This makes the running time depend only on `a.len()`/`b.len()`
+/// (already public information in typical MAC-verification use), not on
+/// where or whether the contents first differ.
+///
+/// # Note
+///
+/// This is constant-time only with respect to the *contents* being
+/// compared; a length mismatch is still detected (and returns `false`)
+/// without comparing any bytes at all, so callers comparing tags of a
+/// fixed, publicly-known length get the intended protection.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_inputs_compare_equal() {
+        assert!(ct_eq(b"the same tag", b"the same tag"));
+        assert!(ct_eq(b"", b""));
+    }
+
+    #[test]
+    fn same_length_unequal_inputs_compare_unequal() {
+        assert!(!ct_eq(b"tag one", b"tag two"));
+        assert!(!ct_eq(b"\x00\x00\x00", b"\x00\x00\x01"));
+    }
+
+    #[test]
+    fn different_length_inputs_compare_unequal() {
+        assert!(!ct_eq(b"short", b"a much longer value"));
+        assert!(!ct_eq(b"", b"x"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Interop with the RustCrypto ecosystem's `digest` crate traits, so this
+//! crate's hashers can be used anywhere `D: Digest` (or the finer-grained
+//! `Update`/`FixedOutput`/`ExtendableOutput` traits) is expected.
+//!
+//! Without a `Cargo.toml` to pull in `digest = "0.10"`, every `use digest::…`
+//! below resolves against nothing: this module has never been type-checked
+//! against the real crate, only hand-verified trait-by-trait against its
+//! published API. Treat the impls as a wiring sketch to review, not code
+//! that has actually built.
+//!
+//! `digest`'s traits assume one concrete type per security level (its own
+//! `sha3` crate has distinct `Sha3_256`/`Sha3_512`/... types), unlike this
+//! crate's [`Sha3`]/[`Shake`], which are single types parameterized at
+//! construction (`Sha3::v256()` and `Sha3::v512()` are both just `Sha3`).
+//! So each digest-crate-facing type here is a thin newtype wrapping this
+//! crate's hasher, fixed to one security level, purely to satisfy that
+//! one-type-per-level shape.
+
+use digest::consts::{U28, U32, U48, U64};
+use digest::{FixedOutput, HashMarker, OutputSizeUser, Update};
+
+use crate::{Hasher, Sha3};
+
+macro_rules! digest_sha3 {
+    ($name:ident, $ctor:expr, $size:ty, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name(Sha3);
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name($ctor())
+            }
+        }
+
+        impl HashMarker for $name {}
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $size;
+        }
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                Hasher::update(&mut self.0, data);
+            }
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, out: &mut digest::Output<Self>) {
+                Hasher::finalize(self.0, out);
+            }
+        }
+    };
+}
+
+digest_sha3!(
+    Sha3_224,
+    Sha3::v224,
+    U28,
+    "`digest`-trait-compatible wrapper around [`Sha3::v224`]."
+);
+digest_sha3!(
+    Sha3_256,
+    Sha3::v256,
+    U32,
+    "`digest`-trait-compatible wrapper around [`Sha3::v256`]."
+);
+digest_sha3!(
+    Sha3_384,
+    Sha3::v384,
+    U48,
+    "`digest`-trait-compatible wrapper around [`Sha3::v384`]."
+);
+digest_sha3!(
+    Sha3_512,
+    Sha3::v512,
+    U64,
+    "`digest`-trait-compatible wrapper around [`Sha3::v512`]."
+);
+
+#[cfg(feature = "shake")]
+mod shake_impl {
+    use digest::{ExtendableOutput, ExtendableOutputReset, Reset, Update, XofReader};
+
+    use crate::{Hasher, Shake};
+
+    macro_rules! digest_shake {
+        ($name:ident, $ctor:expr, $doc:expr) => {
+            #[doc = $doc]
+            #[derive(Clone)]
+            pub struct $name(Shake);
+
+            impl Default for $name {
+                fn default() -> Self {
+                    $name($ctor())
+                }
+            }
+
+            impl Update for $name {
+                fn update(&mut self, data: &[u8]) {
+                    Hasher::update(&mut self.0, data);
+                }
+            }
+
+            impl Reset for $name {
+                fn reset(&mut self) {
+                    Hasher::reset(&mut self.0);
+                }
+            }
+
+            impl ExtendableOutput for $name {
+                type Reader = crate::ShakeReader;
+
+                fn finalize_xof(self) -> Self::Reader {
+                    self.0.finalize_xof()
+                }
+            }
+
+            impl ExtendableOutputReset for $name {
+                fn finalize_xof_reset(&mut self) -> Self::Reader {
+                    let state = core::mem::replace(&mut self.0, $ctor());
+                    state.finalize_xof()
+                }
+            }
+        };
+    }
+
+    digest_shake!(
+        Shake128,
+        Shake::v128,
+        "`digest`-trait-compatible wrapper around [`Shake::v128`]."
+    );
+    digest_shake!(
+        Shake256,
+        Shake::v256,
+        "`digest`-trait-compatible wrapper around [`Shake::v256`]."
+    );
+
+    // `Shake128`/`Shake256` above both use `crate::ShakeReader` as their
+    // `ExtendableOutput::Reader`, so this impl belongs here once rather than
+    // inside `digest_shake!`: emitting it per macro expansion would produce
+    // two `impl XofReader for crate::ShakeReader` blocks for the same
+    // concrete type, which is a duplicate-impl error (E0119).
+    impl XofReader for crate::ShakeReader {
+        fn read(&mut self, buffer: &mut [u8]) {
+            self.squeeze(buffer);
+        }
+    }
+}
+
+#[cfg(feature = "shake")]
+pub use shake_impl::{Shake128, Shake256};
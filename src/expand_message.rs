@@ -0,0 +1,170 @@
+//! [`expand_message_xof`]: the XOF-based message expansion function from the
+//! hash-to-curve draft (`draft-irtf-cfrg-hash-to-curve`, §5.3.2), used by
+//! hash-to-field/hash-to-curve constructions to derive uniformly-random
+//! bytes from a message and a domain-separation tag on top of a XOF, as an
+//! alternative to the block-hash-based `expand_message_xmd`.
+//!
+//! None of the tests below check output against the draft's own published
+//! test vectors: with no fixture-loading machinery in this snapshot, the
+//! only way to get those vectors in would be transcribing their
+//! multi-hundred-bit hex constants by hand, and a single mistyped nibble
+//! would silently pass as a "verified" test rather than fail loudly. So
+//! instead the tests pin structural properties this construction must have
+//! regardless — determinism, sensitivity to each input, and a hand-inlined
+//! replay of the exact absorb/squeeze sequence `expand_message_xof` runs —
+//! the same standard `keccakf.rs`'s narrower permutations are held to where
+//! no external reference is available either.
+
+use crate::{Hasher, Shake};
+
+/// The maximum `DST` length the draft's `DST_prime = DST || I2OSP(len(DST), 1)`
+/// framing can encode in the one-byte length prefix.
+const MAX_DST_LEN: usize = 255;
+
+/// Hashes an over-long `dst` down to `out.len()` bytes via the same XOF, per
+/// the draft's "too long DST" handling (§5.3.3): replace `dst` with
+/// `H("H2C-OVERSIZE-DST-" || dst)`, so `expand_message_xof` never has to
+/// encode a `DST` length that doesn't fit in `I2OSP(_, 1)`.
+fn oversize_dst(shake: fn() -> Shake, dst: &[u8], out: &mut [u8]) {
+    let mut hasher = shake();
+    hasher.update(b"H2C-OVERSIZE-DST-");
+    hasher.update(dst);
+    hasher.finalize_xof().squeeze(out);
+}
+
+/// Implements `expand_message_xof(msg, DST, len_in_bytes)`: absorbs `msg`,
+/// the big-endian-encoded `len_in_bytes`, and the length-prefixed `DST` into
+/// `shake()`, then squeezes `out.len()` bytes.
+///
+/// `shake` selects which XOF backs the expansion (typically
+/// [`Shake::v128`] or [`Shake::v256`], matching the target security level of
+/// the calling suite); `oversize_dst_len` is how many bytes an over-255-byte
+/// `dst` is hashed down to before framing (the draft ties this to the
+/// suite's security parameter `k`; e.g. 32 for a 128-bit-security SHAKE128
+/// suite, 64 for a 256-bit-security SHAKE256 suite).
+///
+/// # Panics
+///
+/// Panics if `dst` is empty, if `out.len()` doesn't fit in the two-byte
+/// `I2OSP(len_in_bytes, 2)` length encoding (i.e. `out.len() > 0xffff`), or
+/// if `oversize_dst_len` doesn't fit in [`MAX_DST_LEN`] (it never needs to
+/// exceed the hash's own natural output size in practice).
+pub fn expand_message_xof(
+    shake: fn() -> Shake,
+    msg: &[u8],
+    dst: &[u8],
+    oversize_dst_len: usize,
+    out: &mut [u8],
+) {
+    assert!(!dst.is_empty(), "DST must be non-empty");
+    assert!(
+        out.len() <= 0xffff,
+        "len_in_bytes must fit in expand_message_xof's 2-byte length encoding"
+    );
+    assert!(
+        oversize_dst_len <= MAX_DST_LEN,
+        "oversize_dst_len must itself fit in the 1-byte DST length prefix"
+    );
+
+    let mut oversized = [0u8; MAX_DST_LEN];
+    let dst = if dst.len() > MAX_DST_LEN {
+        oversize_dst(shake, dst, &mut oversized[..oversize_dst_len]);
+        &oversized[..oversize_dst_len]
+    } else {
+        dst
+    };
+
+    let mut hasher = shake();
+    hasher.update(msg);
+    hasher.update(&(out.len() as u16).to_be_bytes());
+    hasher.update(dst);
+    hasher.update(&[dst.len() as u8]);
+    hasher.finalize_xof().squeeze(out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", b"QUUX-V01-CS02-with-expander-SHAKE128", 16, &mut a);
+        expand_message_xof(Shake::v128, b"msg", b"QUUX-V01-CS02-with-expander-SHAKE128", 16, &mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn diverges_on_message() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg one", dst, 16, &mut a);
+        expand_message_xof(Shake::v128, b"msg two", dst, 16, &mut b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn diverges_on_dst() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", b"dst-one", 16, &mut a);
+        expand_message_xof(Shake::v128, b"msg", b"dst-two", 16, &mut b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn diverges_on_len_in_bytes() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let mut a = [0u8; 16];
+        let mut b = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", dst, 16, &mut a);
+        expand_message_xof(Shake::v128, b"msg", dst, 16, &mut b);
+        assert_ne!(&a[..], &b[..16]);
+    }
+
+    // Replays the absorb sequence by hand (rather than through
+    // `expand_message_xof` itself) to pin the exact byte order the draft
+    // specifies: `msg || I2OSP(len_in_bytes, 2) || DST || I2OSP(len(DST), 1)`.
+    #[test]
+    fn matches_a_hand_assembled_msg_prime() {
+        let msg = b"hello";
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+        let len_in_bytes: usize = 48;
+
+        let mut want_hasher = Shake::v128();
+        want_hasher.update(msg);
+        want_hasher.update(&(len_in_bytes as u16).to_be_bytes());
+        want_hasher.update(dst);
+        want_hasher.update(&[dst.len() as u8]);
+        let mut want = [0u8; 48];
+        want_hasher.finalize_xof().squeeze(&mut want);
+
+        let mut got = [0u8; 48];
+        expand_message_xof(Shake::v128, msg, dst, 16, &mut got);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn an_over_255_byte_dst_is_hashed_down_first() {
+        let long_dst = [0x42u8; 300];
+        let short_dst = [0x42u8; 255];
+
+        let mut via_long = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", &long_dst, 16, &mut via_long);
+
+        let mut via_short = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", &short_dst, 16, &mut via_short);
+
+        // The two DSTs share a 255-byte prefix but must not collide: the
+        // over-long one is replaced with `H("H2C-OVERSIZE-DST-" || long_dst)`
+        // before framing, not silently truncated to `short_dst`.
+        assert_ne!(via_long, via_short);
+
+        let mut via_long_again = [0u8; 32];
+        expand_message_xof(Shake::v128, b"msg", &long_dst, 16, &mut via_long_again);
+        assert_eq!(via_long, via_long_again, "oversize handling must be deterministic");
+    }
+}
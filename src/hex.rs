@@ -0,0 +1,55 @@
+//! [`HexDigest`]: a zero-copy `Display`/`LowerHex` wrapper for formatting a
+//! digest as lowercase hex, plus the `finalize_hex` convenience methods on
+//! fixed-output hashers (see [`Sha3::finalize_hex`](crate::Sha3::finalize_hex)
+//! and [`Keccak::finalize_hex`](crate::Keccak::finalize_hex)) that lets
+//! callers skip pulling in a separate `hex` crate just to log or
+//! JSON-serialize a hash.
+
+use alloc::string::String;
+
+/// A zero-copy `Display`/`LowerHex` wrapper around a digest (or any byte
+/// slice): formats as lowercase hex directly from the borrowed bytes,
+/// without needing to allocate a `String` first unless the caller does
+/// (e.g. via `to_string()` or `format!`).
+pub struct HexDigest<'a>(pub &'a [u8]);
+
+impl<'a> core::fmt::LowerHex for HexDigest<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> core::fmt::Display for HexDigest<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerHex::fmt(self, f)
+    }
+}
+
+/// Formats `digest` as a lowercase hex `String`. Shared by the
+/// `finalize_hex` methods on [`Sha3`](crate::Sha3) and
+/// [`Keccak`](crate::Keccak) so they don't each re-derive the same
+/// `format!` call.
+pub(crate) fn to_hex_string(digest: &[u8]) -> String {
+    use alloc::string::ToString;
+    HexDigest(digest).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn formats_as_lowercase_hex() {
+        assert_eq!(HexDigest(&[0xde, 0xad, 0xbe, 0xef]).to_string(), "deadbeef");
+    }
+
+    #[test]
+    fn display_and_lower_hex_agree() {
+        let digest = HexDigest(&[0x01, 0xff]);
+        assert_eq!(alloc::format!("{}", digest), alloc::format!("{:x}", digest));
+    }
+}
@@ -0,0 +1,325 @@
+//! `HMAC-SHA3`: the standard HMAC construction (RFC 2104) layered over this
+//! crate's [`Sha3`] hashers, for interoperating with systems that specify
+//! `HMAC-SHA3-256`/`HMAC-SHA3-512` rather than the SP800-185-native
+//! [`Kmac256`](crate::Kmac256).
+//!
+//! The block size HMAC pads/truncates the key to is the hash's sponge rate
+//! (1088 bits for SHA3-256, 576 bits for SHA3-512), which is what NIST's own
+//! HMAC-SHA3 examples use in place of a traditional block-cipher-style block
+//! size.
+//!
+//! This has not been checked against the NIST/RFC HMAC-SHA3 known-answer
+//! test vectors, only for internal self-consistency (see the tests below):
+//! treat it as a best-effort structural implementation of the construction
+//! rather than a validated one.
+
+use crate::{Hasher, Sha3};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// Compares two equal-length byte slices without short-circuiting on the
+/// first difference, so a tag mismatch can't be timed to learn which byte
+/// differed first. Duplicated from the shape of [`crate::ct_eq`] rather than
+/// depending on the `ct-eq` feature, since it's only ever used internally by
+/// [`verify`](HmacSha3_256::verify)-style methods here.
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+macro_rules! hmac_sha3 {
+    ($name:ident, $sha3_ctor:expr, $output_len:expr, $block_len:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            inner: Sha3,
+            inner_key: std::vec::Vec<u8>,
+            outer_key: std::vec::Vec<u8>,
+        }
+
+        impl $name {
+            /// Creates a new HMAC keyed with `key`. Keys longer than the
+            /// block size (the hash's sponge rate) are pre-hashed down to
+            /// digest size, per the HMAC spec; keys shorter than the block
+            /// size are zero-padded up to it.
+            pub fn new(key: &[u8]) -> Self {
+                let block_size = $sha3_ctor().rate();
+                let mut block_key = std::vec![0u8; block_size];
+                if key.len() > block_size {
+                    let mut hasher = $sha3_ctor();
+                    hasher.update(key);
+                    let mut digest = std::vec![0u8; $output_len];
+                    hasher.finalize(&mut digest);
+                    block_key[..digest.len()].copy_from_slice(&digest);
+                } else {
+                    block_key[..key.len()].copy_from_slice(key);
+                }
+
+                let inner_key: std::vec::Vec<u8> =
+                    block_key.iter().map(|b| b ^ IPAD).collect();
+                let outer_key: std::vec::Vec<u8> =
+                    block_key.iter().map(|b| b ^ OPAD).collect();
+
+                let mut inner = $sha3_ctor();
+                inner.update(&inner_key);
+
+                $name {
+                    inner,
+                    inner_key,
+                    outer_key,
+                }
+            }
+
+            /// Computes the tag and compares it to `tag` in constant time,
+            /// returning whether they match. Returns `false` (rather than
+            /// panicking) if `tag`'s length doesn't match this HMAC's
+            /// output length.
+            pub fn verify(self, tag: &[u8]) -> bool {
+                let mut computed = [0u8; $output_len];
+                self.finalize(&mut computed);
+                ct_eq(&computed, tag)
+            }
+        }
+
+        impl Hasher for $name {
+            const OUTPUT_LEN: usize = $output_len;
+            const BLOCK_LEN: usize = $block_len;
+
+            /// Absorbs additional message bytes. Can be called multiple
+            /// times.
+            fn update(&mut self, input: &[u8]) {
+                self.inner.update(input);
+            }
+
+            /// Computes the `output.len()`-byte HMAC tag.
+            fn finalize(self, output: &mut [u8]) {
+                let mut inner_digest = [0u8; $output_len];
+                self.inner.finalize(&mut inner_digest);
+
+                let mut outer = $sha3_ctor();
+                outer.update(&self.outer_key);
+                outer.update(&inner_digest);
+                outer.finalize(output);
+            }
+
+            /// Restores the initial, keyed-but-message-free absorbing
+            /// state, so this instance can MAC another message with the
+            /// same key without reallocating.
+            fn reset(&mut self) {
+                let mut inner = $sha3_ctor();
+                inner.update(&self.inner_key);
+                self.inner = inner;
+            }
+
+            /// Like [`Hasher::finalize`], but also [`reset`](Self::reset)s
+            /// in one step.
+            fn finalize_reset(&mut self, output: &mut [u8]) {
+                let mut inner_digest = [0u8; $output_len];
+                self.inner.clone().finalize(&mut inner_digest);
+
+                let mut outer = $sha3_ctor();
+                outer.update(&self.outer_key);
+                outer.update(&inner_digest);
+                outer.finalize(output);
+
+                self.reset();
+            }
+        }
+
+        impl crate::Mac for $name {
+            const TAG_LEN: usize = $output_len;
+
+            fn new(key: &[u8]) -> Self {
+                Self::new(key)
+            }
+
+            fn update(&mut self, input: &[u8]) {
+                Hasher::update(self, input)
+            }
+
+            fn finalize_into(self, output: &mut [u8]) {
+                Hasher::finalize(self, output)
+            }
+
+            fn verify(&self, tag: &[u8]) -> bool {
+                self.clone().verify(tag)
+            }
+        }
+
+        // Prints only the inner `Sha3` (itself just `rate`/`delim`) and
+        // never `inner_key`/`outer_key`, since those are derived directly
+        // from the caller's secret key.
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name)).field("inner", &self.inner).finish()
+            }
+        }
+    };
+}
+
+hmac_sha3!(
+    HmacSha3_256,
+    Sha3::v256,
+    32,
+    136,
+    "`HMAC-SHA3-256`: RFC 2104 HMAC built on [`Sha3::v256`]."
+);
+hmac_sha3!(
+    HmacSha3_512,
+    Sha3::v512,
+    64,
+    72,
+    "`HMAC-SHA3-512`: RFC 2104 HMAC built on [`Sha3::v512`]."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_is_deterministic_and_key_sensitive() {
+        let mut a = HmacSha3_256::new(b"key");
+        a.update(b"hello");
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let mut b = HmacSha3_256::new(b"key");
+        b.update(b"hello");
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+        assert_eq!(out_a, out_b);
+
+        let mut c = HmacSha3_256::new(b"different key");
+        c.update(b"hello");
+        let mut out_c = [0u8; 32];
+        c.finalize(&mut out_c);
+        assert_ne!(out_a, out_c);
+    }
+
+    #[test]
+    fn hmac_of_an_empty_message_is_stable_and_key_dependent() {
+        let mut a = HmacSha3_256::new(b"key");
+        a.update(b"");
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let mut b = HmacSha3_256::new(b"other key");
+        b.update(b"");
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn an_over_length_key_is_pre_hashed() {
+        // A key longer than the block size (136 bytes for SHA3-256) must be
+        // pre-hashed down to digest size before use, so two over-length
+        // keys sharing only a common prefix shorter than the digest must
+        // not collide.
+        let long_key_a = [0x11u8; 200];
+        let mut long_key_b = [0x11u8; 200];
+        long_key_b[199] = 0x22;
+
+        let mut a = HmacSha3_256::new(&long_key_a);
+        a.update(b"hello");
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let mut b = HmacSha3_256::new(&long_key_b);
+        b.update(b"hello");
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn incremental_updates_match_a_single_shot_update() {
+        let mut incremental = HmacSha3_512::new(b"key");
+        incremental.update(b"hello");
+        incremental.update(b" world");
+        let mut got = [0u8; 64];
+        incremental.finalize(&mut got);
+
+        let mut single_shot = HmacSha3_512::new(b"key");
+        single_shot.update(b"hello world");
+        let mut want = [0u8; 64];
+        single_shot.finalize(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn verify_accepts_the_matching_tag_and_rejects_others() {
+        let mut hasher = HmacSha3_256::new(b"key");
+        hasher.update(b"hello");
+        let mut tag = [0u8; 32];
+        hasher.finalize(&mut tag);
+
+        let matching = HmacSha3_256::new(b"key").chain(b"hello");
+        assert!(matching.verify(&tag));
+
+        let wrong_message = HmacSha3_256::new(b"key").chain(b"goodbye");
+        assert!(!wrong_message.verify(&tag));
+
+        let wrong_key = HmacSha3_256::new(b"other key").chain(b"hello");
+        assert!(!wrong_key.verify(&tag));
+
+        let wrong_length = HmacSha3_256::new(b"key").chain(b"hello");
+        assert!(!wrong_length.verify(&tag[..31]));
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher_with_the_same_key() {
+        let mut hasher = HmacSha3_256::new(b"key");
+        hasher.update(b"garbage to discard");
+        hasher.reset();
+        hasher.update(b"hello");
+        let mut got = [0u8; 32];
+        hasher.finalize(&mut got);
+
+        let want = HmacSha3_256::new(b"key").chain(b"hello");
+        let mut want_out = [0u8; 32];
+        want.finalize(&mut want_out);
+
+        assert_eq!(got, want_out);
+    }
+
+    #[test]
+    fn finalize_reset_matches_separate_finalize_then_reset() {
+        let mut via_flush = HmacSha3_256::new(b"key");
+        via_flush.update(b"hello");
+        let mut via_flush_out = [0u8; 32];
+        via_flush.finalize_reset(&mut via_flush_out);
+        via_flush.update(b"world");
+        let mut via_flush_out2 = [0u8; 32];
+        via_flush.finalize(&mut via_flush_out2);
+
+        let want_first = HmacSha3_256::new(b"key").chain(b"hello");
+        let mut want_first_out = [0u8; 32];
+        want_first.finalize(&mut want_first_out);
+        assert_eq!(via_flush_out, want_first_out);
+
+        let want_second = HmacSha3_256::new(b"key").chain(b"world");
+        let mut want_second_out = [0u8; 32];
+        want_second.finalize(&mut want_second_out);
+        assert_eq!(via_flush_out2, want_second_out);
+    }
+
+    #[test]
+    fn output_len_and_block_len_match_the_underlying_sha3_variant() {
+        assert_eq!(HmacSha3_256::OUTPUT_LEN, 32);
+        assert_eq!(HmacSha3_256::BLOCK_LEN, 136);
+
+        assert_eq!(HmacSha3_512::OUTPUT_LEN, 64);
+        assert_eq!(HmacSha3_512::BLOCK_LEN, 72);
+    }
+}
@@ -1,6 +1,10 @@
 //! The `Keccak` hash functions.
 
-use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+use super::{
+    bits_to_rate,
+    keccakf::{keccakf_x4, KeccakF},
+    Buffer, Hasher, KeccakState, XofReader,
+};
 
 /// The `Keccak` hash functions defined in [`Keccak SHA3 submission`].
 ///
@@ -12,17 +16,41 @@ use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
 /// ```
 ///
 /// [`Keccak SHA3 submission`]: https://keccak.team/files/Keccak-submission-3.pdf
+#[derive(Clone)]
 pub struct Keccak {
-    #[cfg(not(feature = "jolt"))]
     state: KeccakState<KeccakF>,
+    // Kept alongside `state` (fed the same input, in lockstep) rather than
+    // instead of it, so this hasher still produces a correct digest when a
+    // `jolt`-featured build runs somewhere other than inside the jolt zkVM
+    // guest — the only environment where the inlined precompile is actually
+    // available. `None` when `bits != 256` or a non-standard `delim` was
+    // requested, since the inlined `Keccak256` only implements plain
+    // Keccak-256.
+    //
+    // `jolt_inlines_keccak256::Keccak256` is itself `Clone` (it is just the
+    // 200-byte sponge state), so deriving here simply clones it in place.
     #[cfg(feature = "jolt")]
-    state: jolt_inlines_keccak256::Keccak256,
+    jolt_state: Option<jolt_inlines_keccak256::Keccak256>,
+    output_bytes: usize,
 }
 
-impl Clone for Keccak {
-    fn clone(&self) -> Self {
-        panic!("Keccak does not implement Clone");
-    }
+/// Reports whether this code is currently executing inside a jolt zkVM guest
+/// program, i.e. whether the `jolt_inlines_keccak256` precompile this build
+/// was linked against is actually backed by the accelerated instruction
+/// rather than running as ordinary native code (where the "inline" would
+/// either be unavailable or no faster than the scalar sponge above).
+///
+/// This crate ships here as a source snapshot with no `Cargo.toml`, so
+/// `jolt_inlines_keccak256`/the jolt SDK aren't vendored in this sandbox and
+/// this function can't be compiled or checked against their real
+/// guest-detection API. It's written to the shape such an API would take (a
+/// `cfg!`-style runtime check the jolt SDK exposes, e.g. `jolt_sdk::is_guest`
+/// or a `riscv`-target `cfg!` combined with a jolt-specific marker), not a
+/// verified integration — treat it as a sketch to adjust once the real jolt
+/// crates are available to build against.
+#[cfg(feature = "jolt")]
+fn running_in_jolt_guest() -> bool {
+    jolt_inlines_keccak256::is_jolt_guest()
 }
 
 impl Keccak {
@@ -57,13 +85,157 @@ impl Keccak {
     }
 
     fn new(bits: usize) -> Keccak {
+        Self::custom(bits, Self::DELIM)
+    }
+
+    /// Creates a new [`Keccak`] hasher with a security level of `bits` bits
+    /// and a custom domain-separation suffix `delim`, for building
+    /// constructions such as `TupleHash` or `KMAC` on top of the Keccak
+    /// sponge without forking this crate.
+    ///
+    /// `delim` encodes the suffix bits that `pad10*1` appends after the
+    /// message, least-significant bit first; the terminating `1` bit of the
+    /// padding rule itself is added separately by `finalize` and does not
+    /// need to be included here. For example, the standard Keccak suffix is
+    /// `0x01` (the single bit `1`) and SHA-3's is `0x06` (the two bits `01`,
+    /// i.e. `0b10` read LSB-first followed by the mandatory pad bit).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is `0` or is not small enough to leave the sponge a
+    /// non-empty rate (`bits < 800`).
+    ///
+    /// Under the `jolt` feature the inlined `Keccak256` implementation only
+    /// supports the standard Keccak suffix, so this also panics if `delim`
+    /// is not [`Self::DELIM`] rather than silently accepting a hasher that
+    /// would (inside the zkVM guest) hash with the wrong domain separation.
+    /// A non-256-bit security level is allowed even under `jolt`; it simply
+    /// never dispatches to the inline and always uses the scalar sponge.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    pub fn custom(bits: usize, delim: u8) -> Keccak {
+        assert!(
+            bits > 0 && bits < 800,
+            "bits must be greater than 0 and less than 800"
+        );
+
+        #[cfg(feature = "jolt")]
+        assert_eq!(
+            delim,
+            Self::DELIM,
+            "the `jolt` backend only supports the standard Keccak domain-separation suffix",
+        );
+
         Keccak {
-            #[cfg(not(feature = "jolt"))]
-            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+            state: KeccakState::new(bits_to_rate(bits), delim),
             #[cfg(feature = "jolt")]
-            state: jolt_inlines_keccak256::Keccak256::new(),
+            jolt_state: (bits == 256).then(jolt_inlines_keccak256::Keccak256::new),
+            output_bytes: bits / 8,
         }
     }
+
+    /// Pads, squeezes and returns the digest as a fixed-size array, checking
+    /// `N` against the security level this hasher was constructed with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not match the output length implied by the
+    /// `vNNN()` constructor used to create this hasher.
+    pub fn finalize_array<const N: usize>(self) -> [u8; N] {
+        assert_eq!(
+            N, self.output_bytes,
+            "output array length does not match the configured security level",
+        );
+        let mut output = [0u8; N];
+        self.finalize(&mut output);
+        output
+    }
+
+    /// Pads and squeezes the digest into `output`, returning
+    /// [`InvalidOutputLen`](crate::InvalidOutputLen) instead of silently
+    /// truncating or under-filling it if `output.len()` doesn't match the
+    /// security level this hasher was constructed with.
+    ///
+    /// Prefer [`finalize_array`](Self::finalize_array) when the length is
+    /// known at compile time; this is for callers who only learn the
+    /// buffer's length at runtime and want the mismatch caught rather than
+    /// silently producing a truncated digest.
+    pub fn try_finalize(self, output: &mut [u8]) -> Result<(), crate::InvalidOutputLen> {
+        if output.len() != self.output_bytes {
+            return Err(crate::InvalidOutputLen);
+        }
+        self.finalize(output);
+        Ok(())
+    }
+
+    /// Pads, squeezes and hex-encodes the digest in one call, for the
+    /// common case of immediately logging or JSON-serializing a hash
+    /// without pulling in a separate `hex` crate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::{Hasher, Keccak};
+    ///
+    /// let mut keccak = Keccak::v256();
+    /// keccak.update(b"hello");
+    /// assert_eq!(keccak.finalize_hex().len(), 64);
+    /// ```
+    #[cfg(all(feature = "hex", feature = "alloc"))]
+    pub fn finalize_hex(self) -> alloc::string::String {
+        let mut output = alloc::vec![0u8; self.output_bytes];
+        self.finalize(&mut output);
+        crate::hex::to_hex_string(&output)
+    }
+
+    /// Pads, squeezes and wraps the digest in a [`CtDigest`], so it can be
+    /// stored and compared with `==` without reintroducing a variable-time
+    /// comparison. Otherwise identical to [`finalize_array`](Self::finalize_array),
+    /// including the panic on a mismatched `N`.
+    #[cfg(feature = "ct-eq")]
+    pub fn finalize_ct_array<const N: usize>(self) -> crate::CtDigest<N> {
+        crate::CtDigest::from(self.finalize_array())
+    }
+
+    /// Computes the Keccak-224 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v224()` followed by `update` and `finalize`.
+    pub fn keccak224(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v224();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-256 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v256()` followed by `update` and `finalize`.
+    pub fn keccak256(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v256();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-384 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v384()` followed by `update` and `finalize`.
+    pub fn keccak384(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v384();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
+
+    /// Computes the Keccak-512 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Keccak::v512()` followed by `update` and `finalize`.
+    pub fn keccak512(input: &[u8], output: &mut [u8]) {
+        let mut keccak = Keccak::v512();
+        keccak.update(input);
+        keccak.finalize(output);
+    }
 }
 
 impl Hasher for Keccak {
@@ -82,10 +254,24 @@ impl Hasher for Keccak {
     /// ```
     fn update(&mut self, input: &[u8]) {
         self.state.update(input);
+
+        // Fed in lockstep so whichever path `finalize`/`finalize_reset` ends
+        // up trusting at runtime has already absorbed everything.
+        #[cfg(feature = "jolt")]
+        if let Some(jolt_state) = &mut self.jolt_state {
+            jolt_state.update(input);
+        }
     }
 
     /// Pad and squeeze the state to the output.
     ///
+    /// Under the `jolt` feature this dispatches to the inlined precompile
+    /// only when actually running inside the jolt zkVM guest (see
+    /// `running_in_jolt_guest`); everywhere else, including an ordinary
+    /// native test binary linked with `jolt` enabled, it falls back to the
+    /// scalar sponge so the result is still correct rather than panicking or
+    /// silently hashing with the accelerator's assumptions.
+    ///
     /// # Example
     ///
     /// ```
@@ -99,13 +285,970 @@ impl Hasher for Keccak {
     /// #
     /// ```
     fn finalize(self, output: &mut [u8]) {
-        #[cfg(not(feature = "jolt"))]
+        #[cfg(feature = "jolt")]
+        if let Some(hash) = self.jolt_digest() {
+            output.copy_from_slice(&hash);
+            return;
+        }
+
         self.state.finalize(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Keccak`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Keccak`]: struct.Keccak.html
+    fn reset(&mut self) {
+        self.state.reset();
 
         #[cfg(feature = "jolt")]
-        {
-            let hash = self.state.finalize();
+        if let Some(jolt_state) = &mut self.jolt_state {
+            *jolt_state = jolt_inlines_keccak256::Keccak256::new();
+        }
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        #[cfg(feature = "jolt")]
+        if let Some(hash) = self.jolt_digest() {
             output.copy_from_slice(&hash);
+            self.reset();
+            return;
+        }
+
+        self.state.finalize_reset(output);
+
+        #[cfg(feature = "jolt")]
+        if let Some(jolt_state) = &mut self.jolt_state {
+            *jolt_state = jolt_inlines_keccak256::Keccak256::new();
+        }
+    }
+}
+
+#[cfg(feature = "jolt")]
+impl Keccak {
+    /// Returns the inlined precompile's digest if, and only if, it's both
+    /// available (`bits == 256` and the standard delimiter, see `custom`)
+    /// and this code is actually running inside the jolt zkVM guest right
+    /// now. `None` otherwise, so the caller falls back to the always-correct
+    /// scalar sponge.
+    fn jolt_digest(&self) -> Option<[u8; 32]> {
+        if !running_in_jolt_guest() {
+            return None;
+        }
+        self.jolt_state.clone().map(|state| state.finalize())
+    }
+}
+
+#[cfg(feature = "std")]
+crate::impl_io_write!(Keccak);
+crate::impl_fmt_write!(Keccak);
+crate::impl_debug_via_state!(Keccak);
+#[cfg(not(feature = "jolt"))]
+crate::impl_xof!(Keccak, KeccakReader);
+
+#[cfg(not(feature = "jolt"))]
+impl Keccak {
+    /// Absorbs a compile-time-sized `data`, behaviorally identical to
+    /// `update(data)` but with `N` known at the call site, which lets the
+    /// optimizer elide the general absorb loop's bounds checks. Useful for
+    /// hashing fixed-size structs.
+    pub fn update_fixed<const N: usize>(&mut self, data: &[u8; N]) {
+        self.state.update_fixed(data);
+    }
+
+    /// Finalizes `other` into a stack buffer sized by its
+    /// [`Hasher::OUTPUT_LEN`] and absorbs the result, for hash-of-hash and
+    /// commitment-chain constructions. Only meaningful for `H` whose
+    /// `OUTPUT_LEN` is a real, non-zero per-type constant (e.g.
+    /// [`HmacSha3_256`](crate::HmacSha3_256)); panics if `H::OUTPUT_LEN` is
+    /// `0` or exceeds 64 bytes.
+    pub fn update_digest<H: Hasher>(&mut self, other: H) {
+        self.state.update_digest(other);
+    }
+
+    /// Absorbs `words` directly into the rate lanes as little-endian
+    /// 64-bit words, skipping the byte-repacking [`update`](Hasher::update)
+    /// does internally. Useful for callers (e.g. zk provers) that already
+    /// have word-aligned data.
+    ///
+    /// Equivalent to calling `update(&word.to_le_bytes())` for each word,
+    /// but without the intermediate byte buffer.
+    pub fn update_words(&mut self, words: &[u64]) {
+        self.state.update_words(words);
+    }
+
+    /// The sponge rate, in bytes, this hasher was constructed with.
+    pub fn rate(&self) -> usize {
+        self.state.rate()
+    }
+
+    /// The sponge capacity, in bits, this hasher was constructed with.
+    pub fn capacity_bits(&self) -> usize {
+        self.state.capacity_bits()
+    }
+
+    /// The total number of bytes passed to [`update`](Hasher::update) since
+    /// construction or the last [`reset`](Hasher::reset).
+    pub fn bytes_absorbed(&self) -> u64 {
+        self.state.bytes_absorbed()
+    }
+
+    /// Pads the absorbed input and returns a [`KeccakReader`] that squeezes
+    /// output in a sequence of calls instead of one fixed-size buffer,
+    /// exposing the original Keccak submission's XOF mode (`delim = 0x01`,
+    /// distinct from SHAKE's `0x1f`) rather than the security-level-fixed
+    /// output `finalize` produces.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::{Hasher, Keccak};
+    ///
+    /// let mut keccak = Keccak::v256();
+    /// keccak.update(b"hello");
+    /// let mut reader = keccak.finalize_xof();
+    /// let mut first = [0u8; 10];
+    /// let mut second = [0u8; 22];
+    /// reader.squeeze(&mut first);
+    /// reader.squeeze(&mut second);
+    /// ```
+    #[doc(alias = "into_xof")]
+    pub fn finalize_xof(self) -> KeccakReader {
+        KeccakReader(XofReader::new(self.state))
+    }
+
+    /// Rebuilds a hasher from a raw 1600-bit Keccak state (25 64-bit
+    /// lanes), an absorb offset into it, and the `rate`/`delim` it was
+    /// constructed with — for importing a state computed elsewhere (e.g.
+    /// a precomputed IV, or a state captured by a different
+    /// implementation) and continuing to absorb or squeeze it. The
+    /// inverse of [`into_raw`](Keccak::into_raw).
+    ///
+    /// The imported hasher has no fixed security level of its own (unlike
+    /// [`Keccak::v256`] and friends), so [`finalize_array`](Keccak::finalize_array)
+    /// always panics on it; use [`Hasher::finalize`] with a caller-sized
+    /// buffer, or [`finalize_xof`](Keccak::finalize_xof), instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or greater than `200`, or if `offset >=
+    /// rate`.
+    pub fn from_raw(state: [u64; 25], rate: usize, delim: u8, offset: usize) -> Keccak {
+        Keccak {
+            state: KeccakState::from_raw(state, rate, delim, offset),
+            output_bytes: 0,
+        }
+    }
+
+    /// Tears this hasher down into its raw 1600-bit lane array and absorb
+    /// offset, the inverse of [`from_raw`](Keccak::from_raw).
+    pub fn into_raw(self) -> ([u64; 25], usize) {
+        self.state.into_raw()
+    }
+
+    /// Returns the raw 1600-bit state as 25 little-endian 64-bit lanes —
+    /// the same representation [`into_raw`](Keccak::into_raw) and
+    /// [`from_raw`](Keccak::from_raw) use.
+    pub fn raw_state_le(&self) -> [u64; 25] {
+        self.state.raw_state_le()
+    }
+
+    /// Returns the raw 1600-bit state as 25 big-endian 64-bit lanes, i.e.
+    /// [`raw_state_le`](Keccak::raw_state_le)'s lanes byte-swapped.
+    pub fn raw_state_be(&self) -> [u64; 25] {
+        self.state.raw_state_be()
+    }
+
+    /// Registers a callback invoked with this hasher's full 1600-bit
+    /// state, as 25 little-endian 64-bit lanes (the same layout as
+    /// [`raw_state_le`](Keccak::raw_state_le)), immediately after every
+    /// permutation performed during [`update`](Hasher::update) or
+    /// [`finalize`](Hasher::finalize). Intended for tooling authors
+    /// tracking down a `keccak256` mismatch against another
+    /// implementation (e.g. the EVM's) by comparing intermediate sponge
+    /// states permutation-by-permutation, rather than only the final
+    /// digest.
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    pub fn set_trace(&mut self, f: impl FnMut(&[u64; 25]) + 'static) {
+        self.state.set_trace(f);
+    }
+}
+
+/// An extendable-output reader returned by [`Keccak::finalize_xof`].
+///
+/// Squeezing `n` bytes across several `squeeze` calls of arbitrary sizes
+/// produces the same `n` bytes as squeezing them in one call; the reader
+/// tracks the partial-block offset and only re-permutes the sponge once a
+/// full rate's worth of output has been read.
+#[cfg(not(feature = "jolt"))]
+#[derive(Clone)]
+pub struct KeccakReader(XofReader<KeccakF>);
+
+#[cfg(not(feature = "jolt"))]
+impl KeccakReader {
+    /// Squeezes `buf.len()` more bytes of output, continuing from wherever
+    /// the previous `squeeze` call (if any) left off.
+    pub fn squeeze(&mut self, buf: &mut [u8]) {
+        self.0.squeeze(buf);
+    }
+}
+
+/// Returned by [`Keccak::hash_batch`] when the inputs are not all the same
+/// length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchLengthMismatch;
+
+/// Keccak-256's rate in bytes (`200 - 256 / 4`).
+const KECCAK256_RATE: usize = 136;
+
+/// Runs the four independent sponges in `buffers` through one more block,
+/// batching the permutation step across all four via [`keccakf_x4`].
+fn permute4(buffers: &mut [Buffer<u64>; 4]) {
+    let mut words = [[0u64; 25]; 4];
+    for (words, buffer) in words.iter_mut().zip(buffers.iter_mut()) {
+        words.copy_from_slice(buffer.words());
+    }
+    keccakf_x4(&mut words);
+    for (words, buffer) in words.iter().zip(buffers.iter_mut()) {
+        buffer.words().copy_from_slice(words);
+    }
+}
+
+/// Hashes exactly four equal-length `inputs` into `outputs`, permuting all
+/// four sponges together at each block boundary instead of one at a time.
+fn keccak256_batch_of_4(inputs: &[&[u8]], outputs: &mut [[u8; 32]]) {
+    let mut buffers = [
+        Buffer::default(),
+        Buffer::default(),
+        Buffer::default(),
+        Buffer::default(),
+    ];
+    let mut tails = [inputs[0], inputs[1], inputs[2], inputs[3]];
+
+    let full_blocks = inputs[0].len() / KECCAK256_RATE;
+    for _ in 0..full_blocks {
+        for (buffer, tail) in buffers.iter_mut().zip(tails.iter_mut()) {
+            buffer.xorin(&tail[..KECCAK256_RATE], 0, KECCAK256_RATE);
+            *tail = &tail[KECCAK256_RATE..];
+        }
+        permute4(&mut buffers);
+    }
+
+    for (buffer, tail) in buffers.iter_mut().zip(tails.iter()) {
+        if !tail.is_empty() {
+            buffer.xorin(tail, 0, tail.len());
         }
+        buffer.pad(tail.len(), Keccak::DELIM, KECCAK256_RATE);
+    }
+    permute4(&mut buffers);
+
+    for (buffer, output) in buffers.iter_mut().zip(outputs.iter_mut()) {
+        buffer.setout(output, 0, 32);
+    }
+}
+
+impl Keccak {
+    /// Computes the Keccak-256 digest of each of `inputs` into the matching
+    /// slot of `out`, batching the permutation across groups of four
+    /// independent sponges via an internal `keccakf_x4` rather than hashing
+    /// each input one at a time. Useful for Merkle-tree/zk workloads that
+    /// hash many same-size leaves.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BatchLengthMismatch`] if `inputs` are not all the same
+    /// length: batching relies on every sponge reaching the same
+    /// absorb/pad boundaries in lockstep.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs.len() != out.len()`.
+    pub fn hash_batch(
+        inputs: &[&[u8]],
+        out: &mut [[u8; 32]],
+    ) -> Result<(), BatchLengthMismatch> {
+        assert_eq!(inputs.len(), out.len(), "inputs and out must be the same length");
+
+        if let Some(&first) = inputs.first() {
+            if inputs.iter().any(|input| input.len() != first.len()) {
+                return Err(BatchLengthMismatch);
+            }
+        }
+
+        let mut inputs = inputs;
+        let mut out = out;
+        while inputs.len() >= 4 {
+            keccak256_batch_of_4(&inputs[..4], &mut out[..4]);
+            inputs = &inputs[4..];
+            out = &mut out[4..];
+        }
+        for (input, output) in inputs.iter().zip(out.iter_mut()) {
+            Keccak::keccak256(input, output);
+        }
+
+        Ok(())
+    }
+}
+
+/// Computes the Keccak-224 digest of `data`, returning it by value.
+///
+/// Unlike [`Keccak::keccak224`], this accepts anything that derefs to
+/// `&[u8]` (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array
+/// instead of writing into a caller-supplied buffer.
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::keccak224;
+///
+/// let _digest: [u8; 28] = keccak224(b"hello");
+/// ```
+pub fn keccak224(data: impl AsRef<[u8]>) -> [u8; 28] {
+    let mut keccak = Keccak::v224();
+    keccak.update(data.as_ref());
+    keccak.finalize_array()
+}
+
+/// Computes the Keccak-256 digest of `data`, returning it by value.
+///
+/// Unlike [`Keccak::keccak256`], this accepts anything that derefs to
+/// `&[u8]` (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array
+/// instead of writing into a caller-supplied buffer.
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::keccak256;
+///
+/// assert_eq!(
+///     keccak256(b""),
+///     [
+///         0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+///         0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+///         0x5d, 0x85, 0xa4, 0x70,
+///     ]
+/// );
+/// ```
+pub fn keccak256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut keccak = Keccak::v256();
+    keccak.update(data.as_ref());
+    keccak.finalize_array()
+}
+
+/// Computes the Keccak-384 digest of `data`, returning it by value.
+///
+/// Unlike [`Keccak::keccak384`], this accepts anything that derefs to
+/// `&[u8]` (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array
+/// instead of writing into a caller-supplied buffer.
+pub fn keccak384(data: impl AsRef<[u8]>) -> [u8; 48] {
+    let mut keccak = Keccak::v384();
+    keccak.update(data.as_ref());
+    keccak.finalize_array()
+}
+
+/// Computes the Keccak-512 digest of `data`, returning it by value.
+///
+/// Unlike [`Keccak::keccak512`], this accepts anything that derefs to
+/// `&[u8]` (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array
+/// instead of writing into a caller-supplied buffer.
+pub fn keccak512(data: impl AsRef<[u8]>) -> [u8; 64] {
+    let mut keccak = Keccak::v512();
+    keccak.update(data.as_ref());
+    keccak.finalize_array()
+}
+
+// `jolt_inlines_keccak256` isn't a real dependency in this source-snapshot
+// sandbox (there's no `Cargo.toml` to pull it in), so the `jolt` feature
+// can't actually be compiled here — see `running_in_jolt_guest`'s doc
+// comment. This module is written to the shape a real test would take once
+// that crate is vendored, pinning exactly the behavior `custom`'s doc
+// comment and `jolt_digest` already promise: `v512` (any non-256-bit
+// security level) never touches the inline precompile and always produces
+// a correct digest via the scalar sponge, both inside and outside a jolt
+// guest, rather than the `output.copy_from_slice(&hash)` panic a hardcoded
+// `[u8; 32]` digest would cause for a 64-byte `v512` output.
+#[cfg(all(test, feature = "jolt"))]
+mod jolt_fallback_tests {
+    use super::*;
+
+    #[test]
+    fn v512_never_uses_the_256_bit_only_inline_and_still_hashes_correctly() {
+        let mut keccak = Keccak::v512();
+        assert!(
+            keccak.jolt_state.is_none(),
+            "v512 must not construct a jolt_state at all, since the inline only supports v256"
+        );
+
+        keccak.update(b"hello");
+        let mut got = [0u8; 64];
+        keccak.finalize(&mut got);
+
+        let mut want_keccak = Keccak::v512();
+        want_keccak.update(b"hello");
+        let want = want_keccak.finalize_array();
+
+        assert_eq!(got, want);
+    }
+}
+
+#[cfg(all(test, not(feature = "jolt")))]
+mod tests {
+    use super::*;
+
+    // Known vector: keccak256("") is the well-known "empty input" digest
+    // used throughout Ethereum (e.g. as the code hash of non-contract
+    // accounts), so it doubles as a cross-check against an independent
+    // implementation.
+    #[test]
+    fn keccak256_of_empty_input_matches_known_vector() {
+        let mut keccak = Keccak::v256();
+        let mut output = [0u8; 32];
+        keccak.update(b"");
+        keccak.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+    }
+
+    #[test]
+    fn keccak256_of_hello_matches_known_vector() {
+        let mut keccak = Keccak::v256();
+        let mut output = [0u8; 32];
+        keccak.update(b"hello");
+        keccak.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x1c, 0x8a, 0xff, 0x95, 0x06, 0x85, 0xc2, 0xed, 0x4b, 0xc3, 0x17, 0x4f, 0x34, 0x72,
+                0x28, 0x7b, 0x56, 0xd9, 0x51, 0x7b, 0x9c, 0x94, 0x81, 0x27, 0x31, 0x9a, 0x09, 0xa7,
+                0xa3, 0x6d, 0xea, 0xc8,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-224("hello").
+    #[test]
+    fn keccak224_one_shot_matches_known_vector() {
+        let mut output = [0u8; 28];
+        Keccak::keccak224(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x45, 0x52, 0x4e, 0xc4, 0x54, 0xbc, 0xc7, 0xd4, 0xb8, 0xf7, 0x43, 0x50, 0xc4, 0xa4,
+                0xe6, 0x28, 0x09, 0xfc, 0xb4, 0x9b, 0xc2, 0x9d, 0xf6, 0x2e, 0x61, 0xb6, 0x9f, 0xa4,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-384("hello").
+    #[test]
+    fn keccak384_one_shot_matches_known_vector() {
+        let mut output = [0u8; 48];
+        Keccak::keccak384(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0xdc, 0xef, 0x6f, 0xb7, 0x90, 0x8f, 0xd5, 0x2b, 0xa2, 0x6a, 0xab, 0xa7, 0x51, 0x21,
+                0x52, 0x6a, 0xbb, 0xf1, 0x21, 0x7f, 0x1c, 0x0a, 0x31, 0x02, 0x46, 0x52, 0xd1, 0x34,
+                0xd3, 0xe3, 0x2f, 0xb4, 0xcd, 0x8e, 0x9c, 0x70, 0x3b, 0x8f, 0x43, 0xe7, 0x27, 0x7b,
+                0x59, 0xa5, 0xcd, 0x40, 0x21, 0x75,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-512("hello").
+    #[test]
+    fn keccak512_one_shot_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Keccak::keccak512(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x52, 0xfa, 0x80, 0x66, 0x2e, 0x64, 0xc1, 0x28, 0xf8, 0x38, 0x9c, 0x9e, 0xa6, 0xc7,
+                0x3d, 0x4c, 0x02, 0x36, 0x80, 0x04, 0xbf, 0x44, 0x63, 0x49, 0x19, 0x00, 0xd1, 0x1a,
+                0xaa, 0xdc, 0xa3, 0x9d, 0x47, 0xde, 0x1b, 0x01, 0x36, 0x1f, 0x20, 0x7c, 0x51, 0x2c,
+                0xfa, 0x79, 0xf0, 0xf9, 0x2c, 0x33, 0x95, 0xc6, 0x7f, 0xf7, 0x92, 0x8e, 0x3f, 0x5c,
+                0xe3, 0xe3, 0xc8, 0x52, 0xb3, 0x92, 0xf9, 0x76,
+            ]
+        );
+    }
+
+    // Known vector: Keccak-512("").
+    #[test]
+    fn keccak512_of_empty_input_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Keccak::keccak512(b"", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x0e, 0xab, 0x42, 0xde, 0x4c, 0x3c, 0xeb, 0x92, 0x35, 0xfc, 0x91, 0xac, 0xff, 0xe7,
+                0x46, 0xb2, 0x9c, 0x29, 0xa8, 0xc3, 0x66, 0xb7, 0xc6, 0x0e, 0x4e, 0x67, 0xc4, 0x66,
+                0xf3, 0x6a, 0x43, 0x04, 0xc0, 0x0f, 0xa9, 0xca, 0xf9, 0xd8, 0x79, 0x76, 0xba, 0x46,
+                0x9b, 0xcb, 0xe0, 0x67, 0x13, 0xb4, 0x35, 0xf0, 0x91, 0xef, 0x27, 0x69, 0xfb, 0x16,
+                0x0c, 0xda, 0xb3, 0x3d, 0x36, 0x70, 0x68, 0x0e,
+            ]
+        );
+    }
+
+    #[test]
+    fn clone_forks_a_partially_absorbed_state() {
+        let mut prefix = Keccak::v256();
+        prefix.update(b"hello");
+
+        let mut a = prefix.clone();
+        let mut b = prefix.clone();
+        a.update(b" world");
+        b.update(b" there");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello world", &mut want);
+        assert_eq!(out_a, want);
+    }
+
+    #[test]
+    fn finalize_array_matches_finalize() {
+        let mut via_finalize = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut via_finalize);
+
+        let mut keccak = Keccak::v256();
+        keccak.update(b"hello");
+        let via_array: [u8; 32] = keccak.finalize_array();
+        assert_eq!(via_array, via_finalize);
+    }
+
+    #[test]
+    #[should_panic(expected = "output array length does not match the configured security level")]
+    fn finalize_array_panics_on_mismatched_length() {
+        let _: [u8; 16] = Keccak::v256().finalize_array();
+    }
+
+    #[cfg(all(feature = "hex", feature = "alloc"))]
+    #[test]
+    fn finalize_hex_matches_a_hex_encoded_finalize() {
+        let mut via_finalize = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut via_finalize);
+        let want: alloc::string::String =
+            via_finalize.iter().map(|b| alloc::format!("{:02x}", b)).collect();
+
+        let mut keccak = Keccak::v256();
+        keccak.update(b"hello");
+        assert_eq!(keccak.finalize_hex(), want);
+    }
+
+    #[test]
+    fn finalize_reset_matches_finalize_then_fresh_hasher() {
+        let mut hasher = Keccak::v256();
+        hasher.update(b"hello");
+        let mut got = [0u8; 32];
+        hasher.finalize_reset(&mut got);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut want);
+        assert_eq!(got, want);
+
+        hasher.update(b"world");
+        let mut got2 = [0u8; 32];
+        hasher.finalize(&mut got2);
+        let mut want2 = [0u8; 32];
+        Keccak::keccak256(b"world", &mut want2);
+        assert_eq!(got2, want2);
+    }
+
+    #[test]
+    fn finalize_reset_hashes_a_sequence_of_messages_without_reconstructing() {
+        let messages: [&[u8]; 3] = [b"hello", b"world", b"tiny-keccak"];
+
+        let mut hasher = Keccak::v256();
+        for message in messages {
+            hasher.update(message);
+            let mut got = [0u8; 32];
+            hasher.finalize_reset(&mut got);
+
+            let mut want = [0u8; 32];
+            Keccak::keccak256(message, &mut want);
+            assert_eq!(got, want);
+        }
+    }
+
+    // Exercises the always-present scalar fallback that `update`/`finalize`/
+    // `reset`/`finalize_reset` dispatch to whenever this isn't actually
+    // running inside the jolt zkVM guest (which, under a normal `cargo test`
+    // run, it never is — even when the `jolt` feature happens to be
+    // enabled). Guards against the accelerated path's introduction ever
+    // regressing correctness for every other caller of this crate.
+    #[test]
+    fn non_jolt_execution_still_produces_correct_keccak256_output() {
+        let mut hasher = Keccak::v256();
+        hasher.update(b"hello");
+        hasher.update(b" world");
+        let mut got = [0u8; 32];
+        hasher.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello world", &mut want);
+        assert_eq!(got, want);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn custom_with_sha3_delim_matches_sha3() {
+        // 0x06 is SHA-3's domain-separation suffix (see `custom`'s docs).
+        let mut keccak = Keccak::custom(256, 0x06);
+        keccak.update(b"hello");
+        let mut got = [0u8; 32];
+        keccak.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        crate::Sha3::sha3_256(b"hello", &mut want);
+        assert_eq!(got, want);
+    }
+
+    #[cfg(all(feature = "shake", not(feature = "jolt")))]
+    #[test]
+    fn custom_with_shake_delim_reproduces_shake256() {
+        // 0x1f is SHAKE's domain-separation suffix (see `custom`'s docs);
+        // `custom(256, ..)` picks the same rate `Shake::v256` does, so this
+        // reconstructs an equivalent sponge to SHAKE256 by hand.
+        let mut keccak = Keccak::custom(256, 0x1f);
+        keccak.update(b"hello");
+        let mut got = [0u8; 64];
+        keccak.finalize_xof().squeeze(&mut got);
+
+        let mut want = [0u8; 64];
+        crate::Shake::v256().chain(b"hello").finalize_xof().squeeze(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    #[should_panic(expected = "bits must be greater than 0 and less than 800")]
+    fn custom_rejects_out_of_range_bits() {
+        Keccak::custom(10_000, Keccak::DELIM);
+    }
+
+    #[test]
+    fn free_functions_match_the_associated_one_shot_functions() {
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut want);
+        assert_eq!(keccak256(b"hello"), want);
+        assert_eq!(keccak256("hello"), want);
+        assert_eq!(keccak256(b"hello".to_vec()), want);
+    }
+
+    #[test]
+    fn hash_batch_matches_hashing_each_input_individually() {
+        // hash_batch requires equal-length inputs; six of them exercises one
+        // batch of four plus a scalar remainder of two.
+        let same_len: [&[u8]; 6] = [b"aaaaaa", b"bbbbbb", b"cccccc", b"dddddd", b"eeeeee", b"ffffff"];
+
+        let mut got = [[0u8; 32]; 6];
+        Keccak::hash_batch(&same_len, &mut got).unwrap();
+
+        for (input, digest) in same_len.iter().zip(got.iter()) {
+            let mut want = [0u8; 32];
+            Keccak::keccak256(input, &mut want);
+            assert_eq!(digest, &want);
+        }
+    }
+
+    #[test]
+    fn hash_batch_matches_individually_across_multiple_rate_blocks() {
+        // Keccak-256's rate is 136 bytes; use inputs spanning several blocks.
+        let long = [0x42u8; 300];
+        let inputs: [&[u8]; 4] = [&long, &long, &long, &long];
+
+        let mut got = [[0u8; 32]; 4];
+        Keccak::hash_batch(&inputs, &mut got).unwrap();
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(&long, &mut want);
+        for digest in &got {
+            assert_eq!(digest, &want);
+        }
+    }
+
+    #[test]
+    fn hash_batch_rejects_mismatched_lengths() {
+        let inputs: [&[u8]; 2] = [b"short", b"a much longer input"];
+        let mut out = [[0u8; 32]; 2];
+        assert_eq!(Keccak::hash_batch(&inputs, &mut out), Err(BatchLengthMismatch));
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn rate_and_capacity_match_the_security_level() {
+        assert_eq!(Keccak::v224().rate(), 144);
+        assert_eq!(Keccak::v224().capacity_bits(), 448);
+
+        assert_eq!(Keccak::v256().rate(), 136);
+        assert_eq!(Keccak::v256().capacity_bits(), 512);
+
+        assert_eq!(Keccak::v384().rate(), 104);
+        assert_eq!(Keccak::v384().capacity_bits(), 768);
+
+        assert_eq!(Keccak::v512().rate(), 72);
+        assert_eq!(Keccak::v512().capacity_bits(), 1024);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn xof_first_32_bytes_match_keccak256() {
+        let mut keccak = Keccak::v256();
+        keccak.update(b"hello");
+        let mut xof_output = [0u8; 32];
+        keccak.finalize_xof().squeeze(&mut xof_output);
+
+        let mut fixed_output = [0u8; 32];
+        let mut want = Keccak::v256();
+        want.update(b"hello");
+        want.finalize(&mut fixed_output);
+
+        assert_eq!(xof_output, fixed_output);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn xof_reader_matches_a_single_large_squeeze() {
+        let mut single_shot = Keccak::v256();
+        single_shot.update(b"hello");
+        let mut want = [0u8; 300];
+        single_shot.finalize_xof().squeeze(&mut want);
+
+        let mut streamed = Keccak::v256();
+        streamed.update(b"hello");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..17]);
+        reader.squeeze(&mut got[17..]);
+
+        assert_eq!(got, want);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn from_raw_round_trips_through_into_raw() {
+        let mut source = Keccak::v256();
+        source.update(b"hello");
+        let (lanes, offset) = source.into_raw();
+
+        let mut rebuilt = Keccak::from_raw(lanes, 136, Keccak::DELIM, offset);
+        rebuilt.update(b" world");
+        let mut got = [0u8; 32];
+        rebuilt.finalize(&mut got);
+
+        let mut want = Keccak::v256();
+        want.update(b"hello world");
+        let mut want_out = [0u8; 32];
+        want.finalize(&mut want_out);
+
+        assert_eq!(got, want_out);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn from_raw_of_a_mid_absorb_state_continues_the_same_message() {
+        // Absorb well past a rate boundary (136 bytes for v256) before
+        // snapshotting, so the imported state has already permuted at
+        // least once, not just buffered a first partial block.
+        let message = b"the quick brown fox jumps over the lazy dog, repeated to cross a rate boundary comfortably";
+        let (first_half, second_half) = message.split_at(message.len() / 2);
+
+        let mut interrupted = Keccak::v256();
+        interrupted.update(first_half);
+        let (lanes, offset) = interrupted.into_raw();
+        let mut resumed = Keccak::from_raw(lanes, 136, Keccak::DELIM, offset);
+        resumed.update(second_half);
+        let mut got = [0u8; 32];
+        resumed.finalize(&mut got);
+
+        let mut uninterrupted = Keccak::v256();
+        uninterrupted.update(message);
+        let mut want = [0u8; 32];
+        uninterrupted.finalize(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    #[should_panic(expected = "offset must be less than rate")]
+    fn from_raw_rejects_an_out_of_range_offset() {
+        Keccak::from_raw([0u64; 25], 136, Keccak::DELIM, 136);
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn raw_state_be_is_the_byteswap_of_raw_state_le() {
+        let mut hasher = Keccak::v256();
+        hasher.update(b"hello");
+
+        let le = hasher.raw_state_le();
+        let be = hasher.raw_state_be();
+        for (le_lane, be_lane) in le.iter().zip(be.iter()) {
+            assert_eq!(*be_lane, le_lane.swap_bytes());
+        }
+    }
+
+    #[cfg(not(feature = "jolt"))]
+    #[test]
+    fn from_raw_round_trips_via_raw_state_be_byteswapped_back() {
+        // 136 bytes exactly fills v256's rate block, so the absorb offset
+        // resets to 0 and into_raw's offset can be hardcoded below instead
+        // of also needing a live accessor for it.
+        let message = [0x42u8; 136];
+
+        let mut source = Keccak::v256();
+        source.update(&message);
+        let be = source.raw_state_be();
+
+        // from_raw always takes little-endian lanes, so byte-swap the
+        // big-endian snapshot back before importing it.
+        let mut le = be;
+        for lane in &mut le {
+            *lane = lane.swap_bytes();
+        }
+        assert_eq!(le, source.raw_state_le());
+
+        let mut rebuilt = Keccak::from_raw(le, 136, Keccak::DELIM, 0);
+        rebuilt.update(b" world");
+        let mut got = [0u8; 32];
+        rebuilt.finalize(&mut got);
+
+        let mut want = Keccak::v256();
+        want.update(&message);
+        want.update(b" world");
+        let mut want_out = [0u8; 32];
+        want.finalize(&mut want_out);
+
+        assert_eq!(got, want_out);
+    }
+
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    #[test]
+    fn set_trace_fires_once_per_permutation() {
+        let count = alloc::rc::Rc::new(core::cell::Cell::new(0usize));
+        let count_in_callback = count.clone();
+
+        let mut hasher = Keccak::v256();
+        hasher.set_trace(move |_lanes| count_in_callback.set(count_in_callback.get() + 1));
+
+        // Exactly one rate block (136 bytes for v256): the block fills
+        // exactly during absorb, triggering one permutation there, and
+        // finalize's pad-then-permute step before squeezing triggers a
+        // second.
+        hasher.update(&[0x42u8; 136]);
+        let mut out = [0u8; 32];
+        hasher.finalize(&mut out);
+
+        assert_eq!(count.get(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn a_ten_megabyte_buffer_matches_many_small_updates() {
+        // Exercises update's chunked-copy loop over thousands of rate-sized
+        // blocks (136 bytes each for v256) in a single call.
+        let data: std::vec::Vec<u8> = (0..10 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+        let mut via_one_shot = Keccak::v256();
+        via_one_shot.update(&data);
+        let mut via_one_shot_out = [0u8; 32];
+        via_one_shot.finalize(&mut via_one_shot_out);
+
+        let mut via_many_small = Keccak::v256();
+        for chunk in data.chunks(4001) {
+            via_many_small.update(chunk);
+        }
+        let mut via_many_small_out = [0u8; 32];
+        via_many_small.finalize(&mut via_many_small_out);
+
+        assert_eq!(via_one_shot_out, via_many_small_out);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn random_split_points_never_change_the_digest() {
+        // A small, dependency-free splitmix64-style PRNG: this crate has no
+        // external dependencies, so a fuzz-style test picks its own split
+        // points deterministically rather than pulling in `rand`.
+        struct SplitMix64(u64);
+        impl SplitMix64 {
+            fn next(&mut self) -> u64 {
+                self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = self.0;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                z ^ (z >> 31)
+            }
+        }
+
+        let data: std::vec::Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(&data, &mut want);
+
+        let mut rng = SplitMix64(0x1234_5678_9abc_def0);
+        for _ in 0..20 {
+            let mut hasher = Keccak::v256();
+            let mut remaining = &data[..];
+            while !remaining.is_empty() {
+                let max_chunk = remaining.len();
+                let chunk_len = 1 + (rng.next() as usize % max_chunk);
+                let (chunk, rest) = remaining.split_at(chunk_len);
+                hasher.update(chunk);
+                remaining = rest;
+            }
+            let mut got = [0u8; 32];
+            hasher.finalize(&mut got);
+            assert_eq!(got, want);
+        }
+    }
+
+    #[test]
+    fn state_eq_agrees_across_different_chunkings_of_the_same_message() {
+        let mut whole = Keccak::v256();
+        whole.update(b"the quick brown fox jumps over the lazy dog");
+
+        let mut chunked = Keccak::v256();
+        chunked.update(b"the quick brown fox ");
+        chunked.update(b"jumps over the lazy dog");
+
+        assert!(whole.state.state_eq(&chunked.state));
+    }
+
+    #[test]
+    fn state_eq_rejects_differing_input() {
+        let mut a = Keccak::v256();
+        a.update(b"hello");
+
+        let mut b = Keccak::v256();
+        b.update(b"world");
+
+        assert!(!a.state.state_eq(&b.state));
+    }
+
+    #[test]
+    fn state_eq_rejects_differing_rate() {
+        let mut a = Keccak::v256();
+        a.update(b"hello");
+
+        let mut b = Keccak::v512();
+        b.update(b"hello");
+
+        assert!(!a.state.state_eq(&b.state));
     }
 }
@@ -0,0 +1,531 @@
+//! Keccak-`f[25w]` permutations, generic over lane width.
+//!
+//! The Keccak family is defined for `f[b]` with `b = 25·2^l`, `l = 0..6`,
+//! giving lane widths `w ∈ {1, 2, 4, 8, 16, 32, 64}` and round counts
+//! `nr = 12 + 2l`. This module only supports the byte-aligned lane widths
+//! (`w` a power of two, `w >= 8`), since those are the ones representable by
+//! a Rust unsigned integer type.
+
+use crate::{Buffer, Permutation};
+
+/// An unsigned integer lane type usable in a Keccak-`f[25w]` permutation.
+///
+/// Implemented for `u8`, `u16`, `u32` and `u64`, corresponding to the
+/// `f[200]`, `f[400]`, `f[800]` and `f[1600]` members of the Keccak family.
+pub(crate) trait Lane:
+    Copy
+    + Default
+    + core::ops::BitXor<Output = Self>
+    + core::ops::BitAnd<Output = Self>
+    + core::ops::Not<Output = Self>
+{
+    /// Rotates the lane left by `n` bits.
+    fn rotate_left(self, n: u32) -> Self;
+
+    /// Truncates a 64-bit round constant to this lane's low `BITS` bits.
+    fn from_round_constant(rc: u64) -> Self;
+
+    /// Reverses the lane's byte order (used on big-endian targets).
+    #[cfg(target_endian = "big")]
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_lane {
+    ($ty:ty) => {
+        impl Lane for $ty {
+            fn rotate_left(self, n: u32) -> Self {
+                <$ty>::rotate_left(self, n % <$ty>::BITS)
+            }
+
+            fn from_round_constant(rc: u64) -> Self {
+                rc as $ty
+            }
+
+            #[cfg(target_endian = "big")]
+            fn swap_bytes(self) -> Self {
+                <$ty>::swap_bytes(self)
+            }
+        }
+    };
+}
+
+impl_lane!(u8);
+impl_lane!(u16);
+impl_lane!(u32);
+impl_lane!(u64);
+
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The 24 round constants of `f[1600]` (`nr = 24`). Narrower permutations
+/// with `nr` rounds use the *last* `nr` of these, each truncated to the
+/// lane width, per [`Lane::from_round_constant`].
+const RC: [u64; 24] = [
+    1u64,
+    0x8082u64,
+    0x800000000000808au64,
+    0x8000000080008000u64,
+    0x808bu64,
+    0x80000001u64,
+    0x8000000080008081u64,
+    0x8000000000008009u64,
+    0x8au64,
+    0x88u64,
+    0x80008009u64,
+    0x8000000au64,
+    0x8000808bu64,
+    0x800000000000008bu64,
+    0x8000000000008089u64,
+    0x8000000000008003u64,
+    0x8000000000008002u64,
+    0x8000000000000080u64,
+    0x800au64,
+    0x800000008000000au64,
+    0x8000000080008081u64,
+    0x8000000000008080u64,
+    0x80000001u64,
+    0x8000000080008008u64,
+];
+
+/// A step of the LFSR (over GF(2) with feedback polynomial
+/// `x^8+x^6+x^5+x^4+1`) that generates the round constants' bits, following
+/// the Keccak team's own reference implementation (`LFSR86540` in
+/// `KeccakF-1600-reference.c`) rather than FIPS 202's equivalent but more
+/// awkward-to-transcribe `rc(t)` recursion. Returns the bit produced this
+/// step alongside the LFSR's next state.
+const fn lfsr_step(state: u8) -> (bool, u8) {
+    let bit = state & 1 != 0;
+    let next = if state & 0x80 != 0 {
+        (state << 1) ^ 0x71
+    } else {
+        state << 1
+    };
+    (bit, next)
+}
+
+/// Regenerates [`RC`] from the LFSR above instead of trusting the literal
+/// table by eye: for each round, the LFSR is stepped 7 times, and bit `j`
+/// sets round-constant bit `2^j - 1` when set.
+const fn lfsr_round_constants() -> [u64; 24] {
+    let mut constants = [0u64; 24];
+    let mut state = 1u8;
+    let mut round = 0;
+    while round < 24 {
+        let mut j = 0;
+        while j < 7 {
+            let (bit, next) = lfsr_step(state);
+            state = next;
+            if bit {
+                constants[round] |= 1u64 << ((1usize << j) - 1);
+            }
+            j += 1;
+        }
+        round += 1;
+    }
+    constants
+}
+
+const fn round_constants_match(a: &[u64; 24], b: &[u64; 24]) -> bool {
+    let mut i = 0;
+    while i < 24 {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// A `const` binding forces this comparison to run at compile time (see the
+// similar `bits_to_rate` check in `lib.rs`), so a transcription error in the
+// literal `RC` table above fails the build rather than just a test run.
+const _: () = assert!(
+    round_constants_match(&RC, &lfsr_round_constants()),
+    "RC does not match the round constants derived from the LFSR definition",
+);
+
+/// Applies a single round of θ/ρ/π/χ/ι to a 25-lane state of width `T`,
+/// using round constant `rc` (truncated to `T::BITS` bits by
+/// [`Lane::from_round_constant`]).
+fn keccak_p_round<T: Lane>(a: &mut [T; 25], rc: u64) {
+    let mut array = [T::default(); 5];
+
+    // Theta
+    for x in 0..5 {
+        for y_count in 0..5 {
+            let y = y_count * 5;
+            array[x] = array[x] ^ a[x + y];
+        }
+    }
+
+    for x in 0..5 {
+        for y_count in 0..5 {
+            let y = y_count * 5;
+            a[y + x] = a[y + x] ^ array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+        }
+    }
+
+    // Rho and pi
+    let mut last = a[1];
+    for x in 0..24 {
+        array[0] = a[PI[x]];
+        a[PI[x]] = last.rotate_left(RHO[x]);
+        last = array[0];
+    }
+
+    // Chi
+    for y_step in 0..5 {
+        let y = y_step * 5;
+
+        array.copy_from_slice(&a[y..y + 5]);
+
+        for x in 0..5 {
+            a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & array[(x + 2) % 5]);
+        }
+    }
+
+    // Iota
+    a[0] = a[0] ^ T::from_round_constant(rc);
+}
+
+/// Applies ρ and π to `a` the same way the table-driven loop in
+/// [`keccak_p_round`] does (chaining each lane's pre-rotation value into the
+/// next iteration via `last`), except every rotation amount and destination
+/// index is a literal transcribed from [`RHO`]/[`PI`] instead of an array
+/// lookup. Literal rotation counts let the compiler specialize
+/// [`Lane::rotate_left`] per call site (e.g. lowering straight to a single
+/// `rol`/`ror` instruction) instead of loading the count from memory first;
+/// see `benches/rho_offsets.rs` for a benchmark comparing the two, and
+/// [`rounds_tests::unrolled_rho_pi_matches_the_table_driven_version`] for the
+/// correctness check pinning this transcription against [`keccak_p_round`].
+fn rho_pi_unrolled<T: Lane>(a: &mut [T; 25]) {
+    let mut last = a[1];
+    macro_rules! step {
+        ($dst:literal, $rot:literal) => {
+            let tmp = a[$dst];
+            a[$dst] = last.rotate_left($rot);
+            last = tmp;
+        };
+    }
+    macro_rules! last_step {
+        ($dst:literal, $rot:literal) => {
+            a[$dst] = last.rotate_left($rot);
+        };
+    }
+
+    step!(10, 1);
+    step!(7, 3);
+    step!(11, 6);
+    step!(17, 10);
+    step!(18, 15);
+    step!(3, 21);
+    step!(5, 28);
+    step!(16, 36);
+    step!(8, 45);
+    step!(21, 55);
+    step!(24, 2);
+    step!(4, 14);
+    step!(15, 27);
+    step!(23, 41);
+    step!(19, 56);
+    step!(13, 8);
+    step!(12, 25);
+    step!(2, 43);
+    step!(20, 62);
+    step!(14, 18);
+    step!(22, 39);
+    step!(9, 61);
+    step!(6, 20);
+    last_step!(1, 44);
+}
+
+/// Identical to [`keccak_p_round`], except ρ/π use [`rho_pi_unrolled`]'s
+/// literal rotation amounts instead of indexing [`RHO`]/[`PI`] at runtime.
+fn keccak_p_round_unrolled<T: Lane>(a: &mut [T; 25], rc: u64) {
+    let mut array = [T::default(); 5];
+
+    // Theta
+    for x in 0..5 {
+        for y_count in 0..5 {
+            let y = y_count * 5;
+            array[x] = array[x] ^ a[x + y];
+        }
+    }
+
+    for x in 0..5 {
+        for y_count in 0..5 {
+            let y = y_count * 5;
+            a[y + x] = a[y + x] ^ array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+        }
+    }
+
+    // Rho and pi
+    rho_pi_unrolled(a);
+
+    // Chi
+    for y_step in 0..5 {
+        let y = y_step * 5;
+
+        array.copy_from_slice(&a[y..y + 5]);
+
+        for x in 0..5 {
+            a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & array[(x + 2) % 5]);
+        }
+    }
+
+    // Iota
+    a[0] = a[0] ^ T::from_round_constant(rc);
+}
+
+/// Identical to [`keccak_p`], except each round uses
+/// [`keccak_p_round_unrolled`] (literal ρ/π rotation amounts) instead of
+/// [`keccak_p_round`] (table-driven ρ/π).
+#[cfg_attr(not(feature = "rho-unrolled"), allow(dead_code))] // otherwise only reachable from `rounds_tests`
+pub(crate) fn keccak_p_unrolled<T: Lane>(a: &mut [T; 25], rounds: usize) {
+    for &rc in &RC[24 - rounds..] {
+        keccak_p_round_unrolled(a, rc);
+    }
+}
+
+/// Applies `rounds` rounds of θ/ρ/π/χ/ι to a 25-lane state of width `T`,
+/// taking ρ's rotation offsets mod `T::BITS` and the round constants from the
+/// last `rounds` entries of [`RC`], truncated to `T::BITS` bits.
+///
+/// `pub(crate)` (rather than private) so [`ReducedRoundKeccak`]'s
+/// runtime-chosen `rounds` can call directly into the same round-execution
+/// machinery that [`KeccakFRounds`]'s compile-time `ROUNDS` uses, instead of
+/// duplicating the round loop.
+///
+/// [`ReducedRoundKeccak`]: crate::reduced_rounds::ReducedRoundKeccak
+pub(crate) fn keccak_p<T: Lane>(a: &mut [T; 25], rounds: usize) {
+    for &rc in &RC[24 - rounds..] {
+        keccak_p_round(a, rc);
+    }
+}
+
+/// Runs four independent `f[1600]` permutations, one per entry of `states`.
+///
+/// The four permutations are interleaved round-by-round (rather than
+/// finishing one state fully before starting the next) purely so a future
+/// SIMD implementation can batch the round function across all four states
+/// at once without changing this function's signature; the result today is
+/// scalar and identical to permuting each state on its own.
+pub(crate) fn keccakf_x4(states: &mut [[u64; 25]; 4]) {
+    for &rc in &RC[..24] {
+        for state in states.iter_mut() {
+            keccak_p_round(state, rc);
+        }
+    }
+}
+
+/// The `f[1600]` permutation on 64-bit lanes, executing the last `ROUNDS`
+/// entries of the full 24-round schedule (see [`RC`]). This is what
+/// [`KeccakF`] (the full 24 rounds) and [`KeccakF12`] (the 12-round variant
+/// used by TurboSHAKE and KangarooTwelve) both are, as instantiations of
+/// this single generic permutation rather than hand-duplicated types.
+#[derive(Clone)]
+pub(crate) struct KeccakFRounds<const ROUNDS: usize>;
+
+impl<const ROUNDS: usize> Permutation for KeccakFRounds<ROUNDS> {
+    type Lane = u64;
+
+    fn execute(buffer: &mut Buffer<u64>) {
+        #[cfg(feature = "rho-unrolled")]
+        {
+            keccak_p_unrolled(buffer.words(), ROUNDS);
+            return;
+        }
+
+        #[allow(unreachable_code)]
+        keccak_p(buffer.words(), ROUNDS);
+    }
+
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    fn trace_lanes(buffer: &Buffer<u64>) -> Option<[u64; 25]> {
+        Some(buffer.0)
+    }
+}
+
+/// The full 24-round `f[1600]` permutation, as used by [`Keccak`], [`Sha3`]
+/// and [`Shake`].
+///
+/// [`Keccak`]: crate::Keccak
+/// [`Sha3`]: crate::Sha3
+/// [`Shake`]: crate::Shake
+pub(crate) type KeccakF = KeccakFRounds<24>;
+
+/// The `f[1600]` permutation reduced to 12 rounds, as used by TurboSHAKE and
+/// KangarooTwelve instead of the full 24-round [`KeccakF`].
+pub(crate) type KeccakF12 = KeccakFRounds<12>;
+
+/// Keccak-p[1600, `ROUNDS`]: the `f[1600]` permutation (64-bit lanes) with
+/// an arbitrary, compile-time round count, under the name Keccak-p's own
+/// literature uses. [`KeccakF`] and [`KeccakF12`] are just the two
+/// standardized instantiations of this (24 and 12 rounds respectively);
+/// [`GenericSponge`](crate::GenericSponge) builds a sponge directly on an
+/// arbitrary one, for experimental constructions that don't fit either.
+pub(crate) type KeccakP1600<const ROUNDS: usize> = KeccakFRounds<ROUNDS>;
+
+/// The `f[800]` permutation on 32-bit lanes (`nr = 22`), backing
+/// [`KeccakP800`](crate::KeccakP800).
+#[derive(Clone)]
+pub(crate) struct KeccakFp800;
+
+impl Permutation for KeccakFp800 {
+    type Lane = u32;
+
+    fn execute(buffer: &mut Buffer<u32>) {
+        keccak_p(buffer.words(), 22);
+    }
+}
+
+/// The `f[400]` permutation on 16-bit lanes (`nr = 20`), backing
+/// [`KeccakP400`](crate::KeccakP400).
+#[derive(Clone)]
+pub(crate) struct KeccakFp400;
+
+impl Permutation for KeccakFp400 {
+    type Lane = u16;
+
+    fn execute(buffer: &mut Buffer<u16>) {
+        keccak_p(buffer.words(), 20);
+    }
+}
+
+/// The `f[200]` permutation on 8-bit lanes (`nr = 18`), backing
+/// [`KeccakP200`](crate::KeccakP200) for lightweight/constrained-hash use
+/// cases and small test vectors.
+#[derive(Clone)]
+pub(crate) struct KeccakFp200;
+
+impl Permutation for KeccakFp200 {
+    type Lane = u8;
+
+    fn execute(buffer: &mut Buffer<u8>) {
+        keccak_p(buffer.words(), 18);
+    }
+}
+
+// These exercise `f[800]`/`f[400]` directly, independent of the sponge/pad
+// layer already covered by `keccakp.rs`'s hasher-level tests. They are not
+// checked against the reference implementation's published
+// intermediate-values test vectors for these widths (only `f[1600]` and
+// `f[200]`'s single-permutation vectors are well-known enough to be
+// confident about transcribing by hand), so this only asserts determinism
+// and input sensitivity; treat the narrower permutations as
+// structurally-correct-but-not-independently-validated until someone runs
+// them against the official reference vectors.
+#[cfg(test)]
+mod narrow_permutation_tests {
+    use super::*;
+
+    #[test]
+    fn keccak_f800_permutation_is_deterministic_and_input_sensitive() {
+        let zero = [0u32; 25];
+        let mut one_bit = zero;
+        one_bit[0] = 1;
+
+        let mut zero_out_a = zero;
+        let mut zero_out_b = zero;
+        keccak_p(&mut zero_out_a, 22);
+        keccak_p(&mut zero_out_b, 22);
+        assert_eq!(zero_out_a, zero_out_b);
+
+        keccak_p(&mut one_bit, 22);
+        assert_ne!(zero_out_a, one_bit);
+    }
+
+    #[test]
+    fn keccak_f400_permutation_is_deterministic_and_input_sensitive() {
+        let zero = [0u16; 25];
+        let mut one_bit = zero;
+        one_bit[0] = 1;
+
+        let mut zero_out_a = zero;
+        let mut zero_out_b = zero;
+        keccak_p(&mut zero_out_a, 20);
+        keccak_p(&mut zero_out_b, 20);
+        assert_eq!(zero_out_a, zero_out_b);
+
+        keccak_p(&mut one_bit, 20);
+        assert_ne!(zero_out_a, one_bit);
+    }
+}
+
+#[cfg(test)]
+mod rounds_tests {
+    use super::*;
+
+    #[test]
+    fn keccak_f_matches_the_24_round_schedule_directly() {
+        let mut expected = [0u64; 25];
+        for (i, lane) in expected.iter_mut().enumerate() {
+            *lane = (i as u64 + 1) * 0x0102_0304_0506_0708;
+        }
+        let mut buffer = Buffer(expected);
+
+        keccak_p(&mut expected, 24);
+        KeccakF::execute(&mut buffer);
+        // `Buffer` isn't directly comparable, so read its lanes back out.
+        let mut via_dispatch_lanes = [0u64; 25];
+        via_dispatch_lanes.copy_from_slice(buffer.words());
+
+        assert_eq!(expected, via_dispatch_lanes);
+    }
+
+    #[test]
+    fn reduced_round_variants_use_the_last_n_round_constants() {
+        // KeccakF12's 12 rounds must be RC[12..24], not RC[0..12]: verify by
+        // reproducing both slices manually and confirming only the "last
+        // 12" slice matches the dispatched KeccakF12 permutation.
+        let seed = [0x1234_5678_9abc_def0u64; 25];
+
+        let mut last_twelve = seed;
+        for &rc in &RC[12..24] {
+            keccak_p_round(&mut last_twelve, rc);
+        }
+
+        let mut first_twelve = seed;
+        for &rc in &RC[0..12] {
+            keccak_p_round(&mut first_twelve, rc);
+        }
+
+        let mut via_dispatch = Buffer(seed);
+        KeccakF12::execute(&mut via_dispatch);
+        let mut via_dispatch_lanes = [0u64; 25];
+        via_dispatch_lanes.copy_from_slice(via_dispatch.words());
+
+        assert_eq!(last_twelve, via_dispatch_lanes);
+        assert_ne!(first_twelve, via_dispatch_lanes);
+    }
+
+    #[test]
+    fn unrolled_rho_pi_matches_the_table_driven_version() {
+        for seed in [0u64, 1, 0x0101_0101_0101_0101, u64::MAX, 0x8000_0000_0000_0001] {
+            let mut table_driven = [0u64; 25];
+            for (i, lane) in table_driven.iter_mut().enumerate() {
+                *lane = seed.wrapping_mul(i as u64 + 1);
+            }
+            let mut unrolled = table_driven;
+
+            keccak_p(&mut table_driven, 24);
+            keccak_p_unrolled(&mut unrolled, 24);
+
+            assert_eq!(table_driven, unrolled);
+        }
+    }
+
+    #[test]
+    fn round_constants_match_the_lfsr_derivation() {
+        // Also enforced at compile time (see the `const _` check right
+        // after `RC`'s definition); this pins the same property down as an
+        // ordinary test too, so a `cargo test` run surfaces it without
+        // needing to know to look for a compile-time assertion.
+        assert_eq!(RC, lfsr_round_constants());
+    }
+}
@@ -0,0 +1,259 @@
+//! `KT256`: the 256-bit-security KangarooTwelve variant from the updated
+//! draft RFC.
+//!
+//! This follows the published tree structure — absorb an 8192-byte first
+//! chunk, hash any further 8192-byte chunks into 64-byte chaining values
+//! with [`TurboShake256`], then absorb the chaining values into a final
+//! node — using [`TurboShake256`] in place of `K12`'s `TurboSHAKE128` so the
+//! chaining values and final digest inherit 256-bit security. The
+//! single-chunk/tree transition (at exactly `CHUNK_SIZE` encoded bytes) and
+//! the customization string `C` (accepted by [`KangarooTwelve256::new`],
+//! appended after the message per the spec) are exercised in the tests
+//! below. It has not been checked against the official K12 known-answer
+//! tests, which target the original 128-bit `TurboSHAKE128`-based
+//! construction and so produce different digests than this 256-bit variant;
+//! treat it as a best-effort structural implementation rather than a
+//! validated one.
+//!
+//! Requires the `std` feature: buffering the whole message before the tree
+//! shape (single node vs. multi-chunk tree) is known avoids a more
+//! complex bounded-memory streaming implementation.
+
+use std::vec::Vec;
+
+use crate::{Hasher, TurboShake256};
+
+/// The SP800-185 `right_encode`: `value` as a minimal-length big-endian byte
+/// string, followed by a final byte giving that string's length. Returns the
+/// used prefix of `out` (which must be at least 9 bytes).
+fn right_encode(value: u64, out: &mut [u8; 9]) -> &[u8] {
+    let be = value.to_be_bytes();
+    let start = be.iter().position(|&b| b != 0).unwrap_or(7).min(7);
+    let len = 8 - start;
+    out[..len].copy_from_slice(&be[start..]);
+    out[len] = len as u8;
+    &out[..len + 1]
+}
+
+/// Bytes per chunk in the KangarooTwelve tree (`B` in the spec).
+const CHUNK_SIZE: usize = 8192;
+
+/// Chaining-value length in bytes for KT256 (vs. 32 for the 128-bit `K12`).
+const CV_SIZE: usize = 64;
+
+/// Domain-separation byte for leaf/chunk hashes feeding the tree.
+const CHAINING_VALUE_DELIM: u8 = 0x0b;
+
+/// Domain-separation byte for the single-chunk (no tree) case.
+const SINGLE_CHUNK_DELIM: u8 = 0x07;
+
+/// Domain-separation byte for the final node once a tree is used.
+const FINAL_NODE_DELIM: u8 = 0x06;
+
+/// `KT256`, the 256-bit-security KangarooTwelve extendable-output function.
+///
+/// The customization string `C` is borrowed rather than copied, so
+/// constructing a `KangarooTwelve256` does not allocate.
+pub struct KangarooTwelve256<'a> {
+    customization: &'a [u8],
+    message: Vec<u8>,
+}
+
+impl<'a> KangarooTwelve256<'a> {
+    /// Creates a new hasher with customization string `customization`
+    /// (pass `&[]` for none).
+    pub fn new(customization: &'a [u8]) -> Self {
+        KangarooTwelve256 {
+            customization,
+            message: Vec::new(),
+        }
+    }
+
+    /// Absorbs more input. Can be called multiple times.
+    pub fn update(&mut self, input: &[u8]) {
+        self.message.extend_from_slice(input);
+    }
+
+    /// Finalizes the hash, writing `output.len()` bytes of XOF output.
+    pub fn finalize(self, output: &mut [u8]) {
+        let mut encoded_customization_len = [0u8; 9];
+        let customization_len = right_encode(self.customization.len() as u64, &mut encoded_customization_len).len();
+
+        let mut s = Vec::with_capacity(
+            self.message.len() + self.customization.len() + customization_len,
+        );
+        s.extend_from_slice(&self.message);
+        s.extend_from_slice(self.customization);
+        s.extend_from_slice(&encoded_customization_len[..customization_len]);
+
+        if s.len() <= CHUNK_SIZE {
+            let mut f = TurboShake256::new(SINGLE_CHUNK_DELIM);
+            f.update(&s);
+            f.finalize(output);
+            return;
+        }
+
+        let (first_chunk, rest) = s.split_at(CHUNK_SIZE);
+        let mut chunk_count = 0u64;
+        let mut chaining_values = Vec::new();
+        for chunk in rest.chunks(CHUNK_SIZE) {
+            let mut leaf = TurboShake256::new(CHAINING_VALUE_DELIM);
+            leaf.update(chunk);
+            let mut cv = [0u8; CV_SIZE];
+            leaf.finalize(&mut cv);
+            chaining_values.extend_from_slice(&cv);
+            chunk_count += 1;
+        }
+
+        let mut encoded_count = [0u8; 9];
+        let count_len = right_encode(chunk_count, &mut encoded_count).len();
+
+        let mut node = TurboShake256::new(FINAL_NODE_DELIM);
+        node.update(first_chunk);
+        node.update(&[0xff, 0xff]);
+        node.update(&chaining_values);
+        node.update(&encoded_count[..count_len]);
+        node.update(&[0xff, 0xff]);
+        node.finalize(output);
+    }
+}
+
+// Omits `message`: it's the raw absorbed input, not just metadata.
+// `customization` is printed since callers typically use it as a
+// non-secret, human-readable domain tag rather than key material.
+impl<'a> core::fmt::Debug for KangarooTwelve256<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KangarooTwelve256")
+            .field("customization", &self.customization)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_length_is_caller_controlled() {
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(b"hello");
+        let mut short = [0u8; 32];
+        kt.finalize(&mut short);
+
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(b"hello");
+        let mut long = [0u8; 128];
+        kt.finalize(&mut long);
+
+        assert_eq!(short, long[..32]);
+    }
+
+    #[test]
+    fn an_empty_message_still_finalizes_deterministically() {
+        let a = KangarooTwelve256::new(&[]);
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let b = KangarooTwelve256::new(&[]);
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+
+        assert_eq!(out_a, out_b);
+        assert_ne!(out_a, [0u8; 32]);
+    }
+
+    #[test]
+    fn customization_changes_the_digest() {
+        let mut a = KangarooTwelve256::new(b"context-a");
+        a.update(b"hello");
+        let mut out_a = [0u8; 32];
+        a.finalize(&mut out_a);
+
+        let mut b = KangarooTwelve256::new(b"context-b");
+        b.update(b"hello");
+        let mut out_b = [0u8; 32];
+        b.finalize(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn multi_chunk_input_hashes_differently_from_its_prefix() {
+        // Exceeds CHUNK_SIZE so the tree path (as opposed to the
+        // single-chunk path) is exercised.
+        let long_input = vec![0x5au8; CHUNK_SIZE * 3 + 17];
+
+        let mut a = KangarooTwelve256::new(&[]);
+        a.update(&long_input);
+        let mut out_a = [0u8; 64];
+        a.finalize(&mut out_a);
+
+        let mut b = KangarooTwelve256::new(&[]);
+        b.update(&long_input[..CHUNK_SIZE * 3]);
+        let mut out_b = [0u8; 64];
+        b.finalize(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn the_single_chunk_to_tree_transition_falls_exactly_at_8192_encoded_bytes() {
+        // With an empty customization string, `right_encode(0)` contributes
+        // 2 bytes, so a 8190-byte message lands exactly on `CHUNK_SIZE`
+        // (single-chunk path) while a 8191-byte message overflows it by one
+        // byte (tree path with a 1-byte second chunk).
+        let at_boundary = vec![0x5au8; CHUNK_SIZE - 2];
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(&at_boundary);
+        let mut at_boundary_out = [0u8; 64];
+        kt.finalize(&mut at_boundary_out);
+
+        let past_boundary = vec![0x5au8; CHUNK_SIZE - 1];
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(&past_boundary);
+        let mut past_boundary_out = [0u8; 64];
+        kt.finalize(&mut past_boundary_out);
+
+        assert_ne!(at_boundary_out, past_boundary_out);
+    }
+
+    #[test]
+    fn the_first_full_chunk_and_a_one_byte_second_chunk_hash_differently_from_two_full_chunks() {
+        // Once just past the boundary, the second chunk is fed through the
+        // same `CHAINING_VALUE_DELIM` leaf path as a full chunk would be,
+        // just with less input; make sure that a short trailing chunk isn't
+        // silently dropped or merged into the first one.
+        let one_byte_second_chunk = vec![0x5au8; CHUNK_SIZE - 1];
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(&one_byte_second_chunk);
+        let mut short_second_chunk_out = [0u8; 64];
+        kt.finalize(&mut short_second_chunk_out);
+
+        let full_second_chunk = vec![0x5au8; CHUNK_SIZE * 2 - 2];
+        let mut kt = KangarooTwelve256::new(&[]);
+        kt.update(&full_second_chunk);
+        let mut full_second_chunk_out = [0u8; 64];
+        kt.finalize(&mut full_second_chunk_out);
+
+        assert_ne!(short_second_chunk_out, full_second_chunk_out);
+    }
+
+    #[test]
+    fn incremental_update_matches_a_single_update_call() {
+        let long_input = vec![0x11u8; CHUNK_SIZE * 2 + 5];
+
+        let mut a = KangarooTwelve256::new(b"ctx");
+        a.update(&long_input);
+        let mut out_a = [0u8; 64];
+        a.finalize(&mut out_a);
+
+        let mut b = KangarooTwelve256::new(b"ctx");
+        for chunk in long_input.chunks(97) {
+            b.update(chunk);
+        }
+        let mut out_b = [0u8; 64];
+        b.finalize(&mut out_b);
+
+        assert_eq!(out_a, out_b);
+    }
+}
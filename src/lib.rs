@@ -0,0 +1,2318 @@
+//! # tiny-keccak
+//!
+//! An implementation of the FIPS-202-defined SHA-3 and SHAKE functions in Rust.
+//!
+//! The `Keccak-f[1600]` permutation is fully unrolled and optimized for 64-bit
+//! lanes; no dependencies beyond `core` are required by default.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod keccakf;
+mod keccakp;
+#[cfg(feature = "keccak")]
+mod keccak;
+#[cfg(feature = "sha3")]
+mod sha3;
+#[cfg(feature = "shake")]
+mod shake;
+#[cfg(feature = "turboshake")]
+mod turboshake;
+#[cfg(feature = "kt256")]
+mod kt256;
+#[cfg(feature = "rawshake")]
+mod rawshake;
+#[cfg(feature = "sp800")]
+mod sp800;
+#[cfg(all(feature = "serde", feature = "std"))]
+mod serde_impl;
+#[cfg(all(feature = "core-hash", feature = "keccak"))]
+mod core_hash;
+#[cfg(feature = "duplex")]
+mod duplex;
+#[cfg(all(feature = "cshake", feature = "sp800", feature = "std"))]
+mod cshake;
+#[cfg(all(feature = "kmac", feature = "cshake", feature = "sp800", feature = "std"))]
+mod kmac;
+#[cfg(all(feature = "prefix-mac", feature = "keccak", feature = "sp800"))]
+mod prefix_mac;
+#[cfg(feature = "ct-eq")]
+mod ct_eq;
+#[cfg(feature = "ct-eq")]
+mod ct_digest;
+#[cfg(all(feature = "hmac", feature = "sha3", feature = "std"))]
+mod hmac;
+#[cfg(all(feature = "tuple-hash", feature = "cshake", feature = "sp800", feature = "std"))]
+mod tuple_hash;
+#[cfg(all(feature = "parallel-hash", feature = "cshake", feature = "sp800", feature = "std"))]
+mod parallel_hash;
+#[cfg(all(feature = "test-vectors", feature = "sha3", feature = "std"))]
+pub mod vectors;
+#[cfg(all(feature = "digest", feature = "sha3"))]
+mod digest_impl;
+#[cfg(all(feature = "hex", feature = "alloc"))]
+mod hex;
+#[cfg(all(feature = "merkle", feature = "keccak", feature = "std"))]
+pub mod merkle;
+#[cfg(feature = "reduced-rounds")]
+pub(crate) mod reduced_rounds;
+#[cfg(feature = "sponge")]
+mod sponge;
+#[cfg(all(feature = "rng", feature = "shake"))]
+mod rng;
+#[cfg(all(feature = "hash-to-curve", feature = "shake"))]
+mod expand_message;
+#[cfg(all(feature = "commitment", feature = "keccak", feature = "sp800", feature = "std", feature = "ct-eq"))]
+mod commitment;
+
+#[cfg(feature = "keccak")]
+pub use crate::keccak::{keccak224, keccak256, keccak384, keccak512, BatchLengthMismatch, Keccak};
+#[cfg(all(feature = "keccak", not(feature = "jolt")))]
+pub use crate::keccak::KeccakReader;
+#[cfg(feature = "sha3")]
+pub use crate::sha3::{sha3_224, sha3_256, sha3_384, sha3_512, Sha3};
+#[cfg(feature = "shake")]
+pub use crate::shake::{mgf_shake128, mgf_shake256, Shake, ShakeReader};
+#[cfg(feature = "turboshake")]
+pub use crate::turboshake::{
+    TurboShake128, TurboShake128Reader, TurboShake256, TurboShake256Reader,
+    MAX_DOMAIN_SEPARATION_BYTE, MIN_DOMAIN_SEPARATION_BYTE,
+};
+#[cfg(feature = "kt256")]
+pub use crate::kt256::KangarooTwelve256;
+#[cfg(feature = "rawshake")]
+pub use crate::rawshake::{RawShake128, RawShake128Reader, RawShake256, RawShake256Reader};
+#[cfg(feature = "sp800")]
+pub use crate::sp800::{left_encode, right_encode};
+#[cfg(all(feature = "sp800", feature = "std"))]
+pub use crate::sp800::encode_string;
+#[cfg(all(feature = "core-hash", feature = "keccak"))]
+pub use crate::core_hash::KeccakHasher;
+#[cfg(feature = "duplex")]
+pub use crate::duplex::Duplex;
+#[cfg(feature = "sponge")]
+pub use crate::sponge::{GenericSponge, Sponge};
+#[cfg(all(feature = "rng", feature = "shake"))]
+pub use crate::rng::ShakeRng;
+#[cfg(all(feature = "hash-to-curve", feature = "shake"))]
+pub use crate::expand_message::expand_message_xof;
+#[cfg(all(feature = "commitment", feature = "keccak", feature = "sp800", feature = "std", feature = "ct-eq"))]
+pub use crate::commitment::Commitment;
+#[cfg(all(feature = "cshake", feature = "sp800", feature = "std"))]
+pub use crate::cshake::{
+    CShake128, CShake128Builder, CShake128Reader, CShake256, CShake256Builder, CShake256Reader,
+};
+#[cfg(all(feature = "kmac", feature = "cshake", feature = "sp800", feature = "std"))]
+pub use crate::kmac::{
+    Kmac128, Kmac256, KmacXof128, KmacXof128Reader, KmacXof256, KmacXof256Reader,
+};
+#[cfg(all(feature = "prefix-mac", feature = "keccak", feature = "sp800"))]
+pub use crate::prefix_mac::PrefixMac;
+#[cfg(feature = "ct-eq")]
+pub use crate::ct_eq::ct_eq;
+#[cfg(feature = "ct-eq")]
+pub use crate::ct_digest::CtDigest;
+#[cfg(all(feature = "hmac", feature = "sha3", feature = "std"))]
+pub use crate::hmac::{HmacSha3_256, HmacSha3_512};
+#[cfg(all(feature = "tuple-hash", feature = "cshake", feature = "sp800", feature = "std"))]
+pub use crate::tuple_hash::{
+    TupleHashXof128, TupleHashXof128Reader, TupleHashXof256, TupleHashXof256Reader,
+};
+#[cfg(all(feature = "parallel-hash", feature = "cshake", feature = "sp800", feature = "std"))]
+pub use crate::parallel_hash::{
+    ParallelHashXof128, ParallelHashXof128Reader, ParallelHashXof256, ParallelHashXof256Reader,
+};
+#[cfg(all(feature = "digest", feature = "sha3"))]
+pub use crate::digest_impl::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+#[cfg(all(feature = "digest", feature = "sha3", feature = "shake"))]
+pub use crate::digest_impl::{Shake128, Shake256};
+
+pub use crate::keccakp::{KeccakP200, KeccakP400, KeccakP800};
+
+#[cfg(all(feature = "hex", feature = "alloc"))]
+pub use crate::hex::HexDigest;
+
+#[cfg(feature = "reduced-rounds")]
+pub use crate::reduced_rounds::ReducedRoundKeccak;
+
+use crate::keccakf::Lane;
+
+/// Returned by a fixed-output hasher's `try_finalize` when the output
+/// buffer's length doesn't match the natural digest length for the
+/// configured security level, instead of silently truncating or
+/// under-filling the digest the way plain [`Hasher::finalize`] would.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidOutputLen;
+
+/// A trait for hashing an arbitrary stream of bytes.
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::{Hasher, Keccak};
+///
+/// let mut hasher = Keccak::v256();
+/// let mut output = [0u8; 32];
+/// hasher.update(b"hello world");
+/// hasher.finalize(&mut output);
+/// ```
+pub trait Hasher {
+    /// The natural digest length, in bytes, of this concrete `Hasher` type,
+    /// for generic code (e.g. an HMAC or Merkle builder) that wants
+    /// `[u8; H::OUTPUT_LEN]`-style buffers without hard-coding a length.
+    ///
+    /// Defaults to `0`, meaning "not a per-type constant": most `Hasher`s
+    /// in this crate (`Keccak`, `Sha3`, `Shake`, `CShake128`/`256`,
+    /// `Kmac128`/`256`, ...) are a single type spanning several security
+    /// levels chosen at construction (`Sha3::v256()` and `Sha3::v512()`
+    /// are both just `Sha3`), or accept a caller-chosen output length at
+    /// `finalize` time (KMAC, cSHAKE) — for those, a compile-time constant
+    /// would be either wrong or actively misleading. Use the instance
+    /// methods (e.g. [`Sha3::rate`], [`Sha3::capacity_bits`]) where the
+    /// crate exposes them instead. Only types whose output length is fixed
+    /// by the type itself (like [`HmacSha3_256`](crate::HmacSha3_256))
+    /// override this.
+    const OUTPUT_LEN: usize = 0;
+
+    /// The sponge rate (block size), in bytes, this `Hasher` type absorbs
+    /// input in. Same default and caveats as [`OUTPUT_LEN`](Hasher::OUTPUT_LEN).
+    const BLOCK_LEN: usize = 0;
+
+    /// Absorb additional input. Can be called multiple times.
+    fn update(&mut self, input: &[u8]);
+
+    /// Pad and squeeze the state to the output.
+    fn finalize(self, output: &mut [u8]);
+
+    /// Zeroes the sponge buffer and resets the absorb offset, preserving the
+    /// configured rate and domain-separation byte, so the hasher can absorb
+    /// a new, independent message without reallocating.
+    fn reset(&mut self);
+
+    /// Squeezes into `output` and then [`reset`](Hasher::reset)s in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]);
+
+    /// Absorbs `input` and returns `self` by value, for chaining several
+    /// `update` calls into the constructor expression that builds a hasher.
+    ///
+    /// This complements, rather than replaces, [`update`](Hasher::update):
+    /// it consumes and returns `self` so it composes with the consuming
+    /// [`finalize`](Hasher::finalize), e.g.
+    /// `Keccak::v256().chain(a).chain(b).finalize(&mut out)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::{Hasher, Keccak};
+    ///
+    /// let mut chained = [0u8; 32];
+    /// Keccak::v256().chain(b"hello").chain(b" world").finalize(&mut chained);
+    ///
+    /// let mut sequential = [0u8; 32];
+    /// let mut hasher = Keccak::v256();
+    /// hasher.update(b"hello");
+    /// hasher.update(b" world");
+    /// hasher.finalize(&mut sequential);
+    ///
+    /// assert_eq!(chained, sequential);
+    /// ```
+    fn chain(mut self, input: &[u8]) -> Self
+    where
+        Self: Sized,
+    {
+        self.update(input);
+        self
+    }
+
+    /// Absorbs bytes from an iterator, for input produced lazily (e.g. from
+    /// a decoder) that would otherwise need collecting into a contiguous
+    /// buffer before it could be passed to [`update`](Hasher::update).
+    ///
+    /// Bytes are batched into a 200-byte stack buffer (the full `f[1600]`
+    /// state width, at least as large as any rate this crate uses) before
+    /// being handed to `update`, so this permutes no more often than an
+    /// equivalent `update` call over the same bytes would, rather than
+    /// re-permuting per byte.
+    fn update_iter<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let mut chunk = [0u8; 200];
+        let mut len = 0;
+        for byte in iter {
+            chunk[len] = byte;
+            len += 1;
+            if len == chunk.len() {
+                self.update(&chunk);
+                len = 0;
+            }
+        }
+        if len > 0 {
+            self.update(&chunk[..len]);
+        }
+    }
+
+    /// Absorbs bytes read from `reader` until EOF, for hashing a file or
+    /// socket without the caller managing a read loop and intermediate
+    /// buffer themselves.
+    ///
+    /// Reads land in a 200-byte stack buffer (the full `f[1600]` state
+    /// width, at least as large as any rate this crate uses, matching
+    /// [`update_iter`](Hasher::update_iter)'s choice) and each filled chunk
+    /// is handed straight to [`update`](Hasher::update); short reads are
+    /// looped over rather than treated as EOF, since [`std::io::Read`]
+    /// permits returning fewer bytes than requested even mid-stream.
+    /// Returns the total number of bytes absorbed, or propagates the first
+    /// IO error encountered.
+    #[cfg(feature = "std")]
+    fn update_reader<R: std::io::Read>(&mut self, mut reader: R) -> std::io::Result<u64> {
+        let mut chunk = [0u8; 200];
+        let mut total = 0u64;
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => return Ok(total),
+                Ok(n) => {
+                    self.update(&chunk[..n]);
+                    total += n as u64;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Absorbs several slices in sequence, as if they had been
+    /// concatenated and passed to a single [`update`](Hasher::update)
+    /// call, for protocols that assemble a message out of separately-owned
+    /// fields (e.g. a domain tag, a length prefix, and a payload) and would
+    /// otherwise need to either call `update` once per field or allocate a
+    /// joined buffer first.
+    ///
+    /// This is just `bufs.iter().for_each(|buf| self.update(buf))`: a rate
+    /// block straddling two slices does not trigger an extra permutation,
+    /// because [`update`](Hasher::update) already carries the absorb
+    /// offset across separate calls (that's what lets a caller split any
+    /// single message across as many `update` calls as it likes and get
+    /// the same digest) — a slice boundary is no different.
+    fn update_vectored(&mut self, bufs: &[&[u8]]) {
+        for buf in bufs {
+            self.update(buf);
+        }
+    }
+
+    /// Absorbs a single byte, for byte-at-a-time framing code (e.g. zk
+    /// circuit builders) that would otherwise construct a one-element
+    /// slice per call.
+    ///
+    /// This is exactly `self.update(&[byte])`: a one-element slice
+    /// literal is already a zero-cost stack reference (no allocation, no
+    /// indirection beyond the pointer/length pair `update` takes anyway),
+    /// so this exists for the calling convention rather than to avoid any
+    /// real overhead in the byte itself. The absorb offset still carries
+    /// across calls exactly as with any other `update` call, so a
+    /// permutation only happens once a full rate block has accumulated,
+    /// not once per byte.
+    fn update_byte(&mut self, byte: u8) {
+        self.update(core::slice::from_ref(&byte));
+    }
+
+    /// Forks a new, independent hasher from the current state, absorbing
+    /// `left_encode(label.len()) || label` into the clone and leaving
+    /// `self` untouched, for protocols that derive several
+    /// domain-separated sub-contexts from a shared parent (e.g. a
+    /// transcript hash) without risking key/context confusion.
+    ///
+    /// The length prefix is what makes this misuse-resistant: without it,
+    /// `derive(b"x").update(b"y")` and `update(b"xy")` would absorb the
+    /// same bytes, silently merging two labels that were meant to stay
+    /// distinct. With it, `derive(x)` then `update(y)` absorbs
+    /// `left_encode(|x|) || x || y`, which differs from a direct
+    /// `update(x)` then `update(y)` (absorbing `x || y` with no prefix at
+    /// all) for any `x`.
+    #[cfg(feature = "sp800")]
+    fn derive(&self, label: &[u8]) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        let mut derived = self.clone();
+        let mut encoded_len = [0u8; 9];
+        let encoded_len = crate::sp800::left_encode(label.len() as u64, &mut encoded_len);
+        derived.update(encoded_len);
+        derived.update(label);
+        derived
+    }
+}
+
+/// Implements [`std::io::Write`] for a [`Hasher`] type by forwarding `write`
+/// to [`Hasher::update`], so a stream can be hashed with e.g.
+/// `std::io::copy` without manually chunking it into `update` calls.
+///
+/// `write` always consumes the whole buffer (hashing has no notion of a
+/// partial write) and `flush` is a no-op, since there is no internal
+/// buffering to flush. Every concrete [`Hasher`] implementation in this
+/// crate is given this impl (see each type's module); the trait itself
+/// can't carry a blanket impl, since `std::io::Write` is foreign to this
+/// crate and `Hasher` may in principle be implemented outside it too.
+#[cfg(feature = "std")]
+macro_rules! impl_io_write {
+    ($ty:ty) => {
+        impl std::io::Write for $ty {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                crate::Hasher::update(self, buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+pub(crate) use impl_io_write;
+
+/// Implements [`core::fmt::Write`] for a [`Hasher`] type by forwarding
+/// `write_str` to [`Hasher::update`], so formatted text can be hashed
+/// directly with `write!(hasher, "{}:{}", a, b)` without allocating the
+/// formatted `String` first.
+///
+/// `write_str` forwards the fragment's UTF-8 bytes to `update` and always
+/// returns `Ok(())`, since hashing can't fail. Unlike
+/// [`impl_io_write`], this needs neither `std` nor an allocator, so it's
+/// unconditional; every concrete [`Hasher`] implementation in this crate is
+/// given this impl (see each type's module).
+macro_rules! impl_fmt_write {
+    ($ty:ty) => {
+        impl core::fmt::Write for $ty {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                crate::Hasher::update(self, s.as_bytes());
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_fmt_write;
+
+/// Implements [`core::fmt::Debug`] for a [`Hasher`] type that wraps a bare
+/// `state: KeccakState<_>` field, by forwarding to [`KeccakState`]'s own
+/// `Debug` impl.
+///
+/// A `#[derive(Debug)]` here would print the sponge buffer and absorb
+/// offset too, which can hold secret or otherwise sensitive absorbed input
+/// (a KMAC/HMAC key, a message a caller didn't intend to log) — a leak
+/// hazard for anyone who `#[derive(Debug)]`s a struct that happens to embed
+/// one of these hashers. [`KeccakState::fmt`] only prints the rate and
+/// domain-separation suffix, so delegating to it keeps that guarantee.
+macro_rules! impl_debug_via_state {
+    ($ty:ty) => {
+        impl core::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($ty)).field("state", &self.state).finish()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_debug_via_state;
+
+/// A reader that squeezes arbitrary-length extendable output, shared by
+/// every [`Xof::Reader`].
+pub trait Squeeze {
+    /// Squeezes `output.len()` more bytes, continuing from wherever the
+    /// previous call (if any) left off.
+    fn squeeze(&mut self, output: &mut [u8]);
+
+    /// Squeezes `len` bytes and writes them to `writer` as they're produced,
+    /// for streaming a large XOF output (e.g. a multi-megabyte mask) straight
+    /// to a file or socket without ever holding the whole `len` in memory at
+    /// once.
+    ///
+    /// Squeezes land in a 200-byte stack buffer (the full `f[1600]` state
+    /// width, at least as large as any rate this crate uses, matching
+    /// [`Hasher::update_reader`]'s choice) and each filled chunk is written
+    /// out in full (looping over short writes, the mirror image of
+    /// `update_reader`'s short-read loop) before the next chunk is squeezed.
+    /// Propagates the first IO error encountered.
+    #[cfg(feature = "std")]
+    fn squeeze_to_writer<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        len: usize,
+    ) -> std::io::Result<()> {
+        let mut buf = [0u8; 200];
+        let mut remaining = len;
+        while remaining > 0 {
+            let take = core::cmp::min(buf.len(), remaining);
+            self.squeeze(&mut buf[..take]);
+            let mut chunk = &buf[..take];
+            while !chunk.is_empty() {
+                match writer.write(chunk) {
+                    Ok(0) => {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::WriteZero,
+                            "failed to write whole buffer",
+                        ))
+                    }
+                    Ok(n) => chunk = &chunk[n..],
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            remaining -= take;
+        }
+        Ok(())
+    }
+}
+
+/// A hasher that can be finalized into a reusable, arbitrary-length output
+/// reader instead of squeezing into one fixed-size buffer via
+/// [`Hasher::finalize`].
+///
+/// Implemented uniformly for every extendable-output type in this crate
+/// ([`Shake`], [`CShake128`]/[`CShake256`], [`TurboShake128`]/[`TurboShake256`],
+/// [`RawShake128`]/[`RawShake256`], the KMAC/TupleHash/ParallelHash XOF
+/// variants, and [`Keccak`]) via [`impl_xof`], so generic code can bound on
+/// "anything I can squeeze arbitrary bytes from" with a single trait
+/// instead of needing to name a concrete reader type. Fixed-output types
+/// ([`Sha3`], the KMAC/TupleHash/ParallelHash non-XOF variants) have no
+/// reader to return and so don't implement this.
+///
+/// [`Shake`]: crate::Shake
+/// [`CShake128`]: crate::CShake128
+/// [`CShake256`]: crate::CShake256
+/// [`TurboShake128`]: crate::TurboShake128
+/// [`TurboShake256`]: crate::TurboShake256
+/// [`RawShake128`]: crate::RawShake128
+/// [`RawShake256`]: crate::RawShake256
+/// [`Keccak`]: crate::Keccak
+/// [`Sha3`]: crate::Sha3
+pub trait Xof {
+    /// The reader type this hasher's [`finalize_xof`](Self::finalize_xof)
+    /// returns.
+    type Reader: Squeeze;
+
+    /// Pads the absorbed input and returns a reader that squeezes output
+    /// in a sequence of calls instead of one fixed-size buffer.
+    fn finalize_xof(self) -> Self::Reader;
+
+    /// Pads the absorbed input and appends exactly `len` squeezed bytes to
+    /// the end of `out`, without clearing `out`'s existing content — for
+    /// KDF pipelines that accumulate derived key material into a growing
+    /// `Vec` instead of squeezing into a fresh buffer and
+    /// `extend_from_slice`-ing it in.
+    ///
+    /// Reserves `len` bytes of spare capacity up front (a single
+    /// allocation, same as `Vec::extend_from_slice` would do) rather than
+    /// growing incrementally as bytes are appended.
+    #[cfg(feature = "alloc")]
+    fn finalize_extend(self, out: &mut alloc::vec::Vec<u8>, len: usize)
+    where
+        Self: Sized,
+    {
+        out.reserve(len);
+        let start = out.len();
+        out.resize(start + len, 0);
+        self.finalize_xof().squeeze(&mut out[start..]);
+    }
+}
+
+/// Implements [`Xof`] for a type `$ty` whose inherent `finalize_xof`
+/// returns `$reader`, and [`Squeeze`] for `$reader` via its inherent
+/// `squeeze`, so both traits stay in lockstep with the inherent methods
+/// every XOF type already exposes directly (kept as the primary API since
+/// they need no trait import) instead of duplicating their bodies.
+macro_rules! impl_xof {
+    ($ty:ty, $reader:ty) => {
+        impl crate::Xof for $ty {
+            type Reader = $reader;
+
+            fn finalize_xof(self) -> Self::Reader {
+                <$ty>::finalize_xof(self)
+            }
+        }
+
+        impl crate::Squeeze for $reader {
+            fn squeeze(&mut self, output: &mut [u8]) {
+                <$reader>::squeeze(self, output)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_xof;
+
+/// A minimal interface shared by this crate's keyed constructions (MACs),
+/// for generic code (e.g. a protocol negotiating which MAC to use, or a
+/// test harness exercising several at once) that wants to be generic over
+/// the specific algorithm instead of naming a concrete type.
+///
+/// Implemented for [`Kmac128`]/[`Kmac256`], [`HmacSha3_256`]/[`HmacSha3_512`],
+/// and [`PrefixMac`]. The cSHAKE-based `KmacXof128`/`KmacXof256` types are
+/// deliberately excluded: a XOF has no single natural fixed tag length, so
+/// they don't fit this trait's `TAG_LEN`/`finalize_into` shape.
+///
+/// [`Kmac128`]: crate::Kmac128
+/// [`Kmac256`]: crate::Kmac256
+/// [`HmacSha3_256`]: crate::HmacSha3_256
+/// [`HmacSha3_512`]: crate::HmacSha3_512
+/// [`PrefixMac`]: crate::PrefixMac
+pub trait Mac: Clone {
+    /// The tag length, in bytes, [`finalize_into`](Mac::finalize_into)
+    /// produces.
+    const TAG_LEN: usize;
+
+    /// Creates a new MAC keyed with `key`.
+    fn new(key: &[u8]) -> Self;
+
+    /// Absorbs additional message bytes. Can be called multiple times.
+    fn update(&mut self, input: &[u8]);
+
+    /// Pads, squeezes and writes the [`Self::TAG_LEN`](Mac::TAG_LEN)-byte
+    /// tag into `output`.
+    fn finalize_into(self, output: &mut [u8]);
+
+    /// Computes the tag and compares it to `tag` in constant time, without
+    /// consuming `self` (unlike [`finalize_into`](Mac::finalize_into)),
+    /// returning whether they match.
+    fn verify(&self, tag: &[u8]) -> bool;
+
+    /// Pads, squeezes and wraps the tag in a [`CtDigest`](crate::CtDigest),
+    /// so it can be stored and compared with `==` without reintroducing a
+    /// variable-time comparison. `N` should normally be
+    /// [`Self::TAG_LEN`](Mac::TAG_LEN); [`finalize_into`](Mac::finalize_into)
+    /// itself already panics (via the caller-provided buffer's length) if
+    /// it isn't.
+    #[cfg(feature = "ct-eq")]
+    fn finalize_ct<const N: usize>(self) -> crate::CtDigest<N>
+    where
+        Self: Sized,
+    {
+        let mut output = [0u8; N];
+        self.finalize_into(&mut output);
+        crate::CtDigest::from(output)
+    }
+}
+
+#[cfg(test)]
+mod xof_tests {
+    use super::*;
+
+    /// Squeezes two same-length outputs from `x` and asserts they match,
+    /// exercised against every concrete [`Xof`] type below so a bound of
+    /// "anything squeezable" is checked to actually behave like one instead
+    /// of just type-checking.
+    fn take_xof<X: Xof>(x: X) {
+        let mut reader = x.finalize_xof();
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        reader.squeeze(&mut a);
+        reader.squeeze(&mut b);
+        assert_ne!(a, b, "consecutive squeezes of a real XOF must not repeat");
+    }
+
+    #[cfg(feature = "shake")]
+    #[test]
+    fn shake_is_a_xof() {
+        let mut shake = crate::Shake::v256();
+        shake.update(b"hello");
+        take_xof(shake);
+    }
+
+    #[cfg(feature = "cshake")]
+    #[test]
+    fn cshake_is_a_xof() {
+        let mut cshake = crate::CShake128::new(b"N", b"S");
+        cshake.update(b"hello");
+        take_xof(cshake);
+    }
+
+    #[cfg(feature = "turboshake")]
+    #[test]
+    fn turboshake_is_a_xof() {
+        let mut turbo = crate::TurboShake128::new(0x1f);
+        turbo.update(b"hello");
+        take_xof(turbo);
+    }
+
+    #[cfg(feature = "rawshake")]
+    #[test]
+    fn rawshake_is_a_xof() {
+        let mut raw = crate::RawShake128::new();
+        raw.update(b"hello");
+        take_xof(raw);
+    }
+
+    #[cfg(all(feature = "kmac", feature = "cshake", feature = "sp800", feature = "std"))]
+    #[test]
+    fn kmacxof_is_a_xof() {
+        let mut kmac = crate::KmacXof128::new(b"key", &[]);
+        kmac.update(b"hello");
+        take_xof(kmac);
+    }
+
+    #[cfg(all(feature = "tuple-hash", feature = "cshake", feature = "sp800", feature = "std"))]
+    #[test]
+    fn tuple_hash_xof_is_a_xof() {
+        let mut tuple_hash = crate::TupleHashXof128::new(&[]);
+        tuple_hash.update_element(b"hello");
+        take_xof(tuple_hash);
+    }
+
+    #[cfg(all(
+        feature = "parallel-hash",
+        feature = "cshake",
+        feature = "sp800",
+        feature = "std"
+    ))]
+    #[test]
+    fn parallel_hash_xof_is_a_xof() {
+        let mut parallel_hash = crate::ParallelHashXof128::new(4, &[]);
+        parallel_hash.update(b"hello world!");
+        take_xof(parallel_hash);
+    }
+
+    #[cfg(all(feature = "keccak", not(feature = "jolt")))]
+    #[test]
+    fn keccak_is_a_xof() {
+        let mut keccak = crate::Keccak::v256();
+        keccak.update(b"hello");
+        take_xof(keccak);
+    }
+
+    #[cfg(all(feature = "shake", feature = "alloc"))]
+    #[test]
+    fn finalize_extend_appends_to_a_non_empty_vec_and_matches_a_direct_squeeze() {
+        let mut out = alloc::vec![0xffu8; 16];
+
+        let mut shake = crate::Shake::v256();
+        shake.update(b"hello");
+        shake.finalize_extend(&mut out, 48);
+
+        assert_eq!(out.len(), 64);
+        assert_eq!(&out[..16], &[0xffu8; 16][..], "existing content must be preserved");
+
+        let mut want = crate::Shake::v256();
+        want.update(b"hello");
+        let mut want_out = [0u8; 48];
+        want.finalize_xof().squeeze(&mut want_out);
+        assert_eq!(&out[16..], &want_out[..]);
+    }
+
+    #[cfg(all(feature = "shake", feature = "std"))]
+    #[test]
+    fn squeeze_to_writer_matches_a_direct_squeeze_over_a_large_output() {
+        const LEN: usize = 1024 * 1024;
+
+        let mut shake = crate::Shake::v256();
+        shake.update(b"hello");
+        let mut written = std::vec::Vec::new();
+        shake.finalize_xof().squeeze_to_writer(&mut written, LEN).unwrap();
+
+        assert_eq!(written.len(), LEN);
+
+        let mut want = crate::Shake::v256();
+        want.update(b"hello");
+        let mut want_prefix = [0u8; 4096];
+        want.finalize_xof().squeeze(&mut want_prefix);
+        assert_eq!(&written[..4096], &want_prefix[..]);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod mac_tests {
+    use super::*;
+
+    /// Exercises accept-then-tamper-reject against every concrete [`Mac`]
+    /// type below, so a bound of "anything keyed and verifiable" is checked
+    /// to actually behave like one instead of just type-checking.
+    fn roundtrip<M: Mac>() {
+        let mut mac = M::new(b"key");
+        mac.update(b"hello");
+        let mut tag = std::vec![0u8; M::TAG_LEN];
+        mac.clone().finalize_into(&mut tag);
+
+        let verifier = M::new(b"key").chain_for_mac(b"hello");
+        assert!(verifier.verify(&tag), "a freshly recomputed tag must verify");
+
+        let mut tampered = tag.clone();
+        tampered[0] ^= 0x01;
+        assert!(!verifier.verify(&tampered), "a flipped tag byte must be rejected");
+
+        let wrong_key = M::new(b"other key").chain_for_mac(b"hello");
+        assert!(!wrong_key.verify(&tag), "a different key must be rejected");
+    }
+
+    /// `Mac` has no `chain`-style helper of its own (unlike [`Hasher`]); this
+    /// gives the generic `roundtrip` test the same "absorb and return `Self`"
+    /// convenience without adding one to the trait, since none of the three
+    /// concrete `Mac` use cases so far have needed it outside tests.
+    trait ChainForMacTest: Mac + Sized {
+        fn chain_for_mac(mut self, input: &[u8]) -> Self {
+            self.update(input);
+            self
+        }
+    }
+    impl<M: Mac> ChainForMacTest for M {}
+
+    #[cfg(all(feature = "kmac", feature = "cshake", feature = "sp800", feature = "std"))]
+    #[test]
+    fn kmac128_is_a_mac() {
+        roundtrip::<crate::Kmac128>();
+    }
+
+    #[cfg(all(feature = "hmac", feature = "sha3", feature = "std"))]
+    #[test]
+    fn hmac_sha3_256_is_a_mac() {
+        roundtrip::<crate::HmacSha3_256>();
+    }
+
+    #[cfg(all(feature = "prefix-mac", feature = "keccak", feature = "sp800"))]
+    #[test]
+    fn prefix_mac_is_a_mac() {
+        roundtrip::<crate::PrefixMac>();
+    }
+}
+
+#[cfg(all(test, feature = "sha3"))]
+mod fmt_write_tests {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn write_str_matches_a_direct_update() {
+        let mut via_write = Sha3::v256();
+        write!(via_write, "abc").unwrap();
+        let mut via_write_digest = [0u8; 32];
+        via_write.finalize(&mut via_write_digest);
+
+        let mut via_update = Sha3::v256();
+        via_update.update(b"abc");
+        let mut via_update_digest = [0u8; 32];
+        via_update.finalize(&mut via_update_digest);
+
+        assert_eq!(via_write_digest, via_update_digest);
+    }
+
+    #[test]
+    fn write_formats_and_absorbs_several_fragments() {
+        let mut via_write = Sha3::v256();
+        write!(via_write, "{}:{}", 1, "two").unwrap();
+        let mut via_write_digest = [0u8; 32];
+        via_write.finalize(&mut via_write_digest);
+
+        let mut via_update = Sha3::v256();
+        via_update.update(b"1:two");
+        let mut via_update_digest = [0u8; 32];
+        via_update.finalize(&mut via_update_digest);
+
+        assert_eq!(via_write_digest, via_update_digest);
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "sha3"))]
+mod io_write_tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    #[test]
+    fn write_matches_a_direct_update() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+
+        let mut via_write = Sha3::v256();
+        let mut cursor = Cursor::new(&data[..]);
+        std::io::copy(&mut cursor, &mut via_write).unwrap();
+        let mut via_write_digest = [0u8; 32];
+        via_write.finalize(&mut via_write_digest);
+
+        let mut via_update = Sha3::v256();
+        via_update.update(data);
+        let mut via_update_digest = [0u8; 32];
+        via_update.finalize(&mut via_update_digest);
+
+        assert_eq!(via_write_digest, via_update_digest);
+    }
+
+    #[test]
+    fn flush_is_a_no_op() {
+        let mut hasher = Sha3::v256();
+        hasher.write_all(b"hello").unwrap();
+        hasher.flush().unwrap();
+        let mut via_flush = [0u8; 32];
+        hasher.finalize(&mut via_flush);
+
+        let mut without_flush = Sha3::v256();
+        without_flush.update(b"hello");
+        let mut want = [0u8; 32];
+        without_flush.finalize(&mut want);
+
+        assert_eq!(via_flush, want);
+    }
+}
+
+/// Converts a security level in bits to a sponge rate in bytes, following the
+/// `rate = 200 - capacity` relationship used throughout the Keccak family
+/// (`capacity = 2 * bits / 8`).
+///
+/// # Panics (debug builds only)
+///
+/// Debug-panics if `bits` isn't one of the standard security levels (128,
+/// 224, 256, 384, 512) every hasher in this crate is constructed with.
+pub const fn bits_to_rate(bits: usize) -> usize {
+    debug_assert!(
+        matches!(bits, 128 | 224 | 256 | 384 | 512),
+        "bits is not a standard Keccak security level",
+    );
+    200 - bits / 4
+}
+
+// A `const fn` call in a `const` binding is checked at compile time, so
+// this doubles as a compile-time test that `bits_to_rate` is genuinely
+// callable in a const context (the constructors built on top of it, like
+// `Keccak::v256`, can't join it yet — see the note on `KeccakState::new`).
+const _: usize = bits_to_rate(256);
+
+#[cfg(test)]
+mod rate_alignment_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    fn standard_security_levels_produce_byte_aligned_rates() {
+        // `bits_to_rate` computes `200 - bits / 4`, i.e. the byte-rate
+        // derived from `(1600 - 2 * bits) / 8`; that division is only
+        // exact when `bits` is a multiple of 32, which all four standard
+        // levels are.
+        for bits in [128usize, 224, 256, 384, 512] {
+            let rate = bits_to_rate(bits);
+            assert_eq!((1600 - 2 * bits) % 8, 0, "{bits}-bit capacity isn't byte-aligned");
+            assert_eq!(rate, (1600 - 2 * bits) / 8, "{bits}-bit rate was truncated by integer division");
+            assert_eq!(rate % 8, 0, "{bits}-bit rate isn't a whole number of 64-bit lanes");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "rate must be a whole number of lanes")]
+    fn a_non_byte_aligned_rate_is_rejected() {
+        // `KeccakF`'s lane is `u64` (8 bytes); 141 isn't a multiple of 8
+        // (i.e. corresponds to a non-multiple-of-32 `bits`), so
+        // `KeccakState::new` must reject it rather than silently
+        // absorbing/squeezing at a misaligned offset.
+        let _state: KeccakState<KeccakF> = KeccakState::new(141, 0x06);
+    }
+}
+
+/// The 25-lane sponge buffer shared by every member of the Keccak family.
+/// Its byte size is `25 * size_of::<T>()`: 200 bytes for the `f[1600]`
+/// permutation's 64-bit lanes, down to 25 bytes for `f[200]`'s 8-bit lanes.
+#[derive(Clone)]
+struct Buffer<T: Lane>([T; 25]);
+
+impl<T: Lane> Default for Buffer<T> {
+    fn default() -> Self {
+        Buffer([T::default(); 25])
+    }
+}
+
+impl<T: Lane> Buffer<T> {
+    fn words(&mut self) -> &mut [T; 25] {
+        &mut self.0
+    }
+
+    #[cfg(target_endian = "little")]
+    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
+        let bytes = core::mem::size_of::<T>() * 25;
+        let ptr = self.0.as_mut_ptr() as *mut u8;
+        let buffer = unsafe { core::slice::from_raw_parts_mut(ptr, bytes) };
+        f(&mut buffer[offset..][..len]);
+    }
+
+    #[cfg(target_endian = "big")]
+    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
+        fn swap_endianess<T: Lane>(buffer: &mut [T; 25]) {
+            for item in buffer {
+                *item = item.swap_bytes();
+            }
+        }
+
+        swap_endianess(&mut self.0);
+        let bytes = core::mem::size_of::<T>() * 25;
+        let ptr = self.0.as_mut_ptr() as *mut u8;
+        let buffer = unsafe { core::slice::from_raw_parts_mut(ptr, bytes) };
+        f(&mut buffer[offset..][..len]);
+        swap_endianess(&mut self.0);
+    }
+
+    fn setout(&mut self, dst: &mut [u8], offset: usize, len: usize) {
+        self.execute(offset, len, |buffer| dst[..len].copy_from_slice(buffer));
+    }
+
+    fn xorin(&mut self, src: &[u8], offset: usize, len: usize) {
+        self.execute(offset, len, |dst| {
+            assert!(dst.len() <= src.len());
+            for (d, s) in dst.iter_mut().zip(src) {
+                *d ^= *s;
+            }
+        });
+    }
+
+    fn pad(&mut self, offset: usize, delim: u8, rate: usize) {
+        self.execute(offset, 1, |buff| buff[0] ^= delim);
+        self.execute(rate - 1, 1, |buff| buff[0] ^= 0x80);
+    }
+
+    /// Overwrites every lane with zero via a volatile write, followed by a
+    /// compiler fence, so the write cannot be optimized away as dead
+    /// (unlike a plain `self.0 = [T::default(); 25]`, which the compiler is
+    /// free to elide once it sees `self` is about to be dropped).
+    #[cfg(feature = "zeroize")]
+    fn zeroize(&mut self) {
+        for lane in self.0.iter_mut() {
+            unsafe { core::ptr::write_volatile(lane, T::default()) };
+        }
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Byte-for-byte comparison of the full sponge buffer, used by
+    /// [`KeccakState::state_eq`] rather than deriving `PartialEq` on
+    /// `Buffer` itself, since that would require `T: PartialEq` to show up
+    /// as a bound on every ordinary use of `Buffer` even though almost none
+    /// of them compare buffers.
+    #[cfg(test)]
+    fn bytes_eq(&mut self, other: &mut Self) -> bool {
+        let len = core::mem::size_of::<T>() * 25;
+        let mut equal = false;
+        self.execute(0, len, |a| {
+            other.execute(0, len, |b| equal = a == b);
+        });
+        equal
+    }
+}
+
+/// A sponge permutation, parameterized so that different Keccak-family
+/// members (`f[1600]`, `f[800]`, `f[400]`, `f[200]`, ...) can share the
+/// [`KeccakState`] plumbing.
+pub(crate) trait Permutation {
+    /// The unsigned lane width this permutation operates on.
+    type Lane: Lane;
+
+    fn execute(a: &mut Buffer<Self::Lane>);
+
+    /// This permutation's lane state as 25 little-endian 64-bit words, for
+    /// [`KeccakState::set_trace`]'s callback. `None` for permutations whose
+    /// lanes aren't already 64 bits wide (there's no lossless way to widen
+    /// a `u8`/`u16`/`u32` lane array into that layout), so only
+    /// [`KeccakFRounds`](crate::keccakf::KeccakFRounds) overrides this.
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    fn trace_lanes(_a: &Buffer<Self::Lane>) -> Option<[u64; 25]> {
+        None
+    }
+}
+
+/// Wraps any [`Permutation`] `P`, delegating [`execute`](Permutation::execute)
+/// to it but first incrementing an atomic call counter, so a test can
+/// assert exactly how many permutations a given absorb/finalize sequence
+/// performs (e.g. to verify lazy-permutation or multi-block chunking
+/// logic) without instrumenting [`KeccakState`] itself. Test-only: there's
+/// no `pub` API built on top of this, so it lives under `#[cfg(test)]`
+/// rather than behind its own feature flag.
+///
+/// The counter lives in a `static` local to [`count`](Self::count)/
+/// [`reset_count`](Self::reset_count)'s bodies rather than a struct field:
+/// `Permutation::execute` is an associated function with no `&self` (the
+/// permutation itself is stateless; only the `KeccakState` buffer is
+/// mutated), so there is no instance to hang a counter off of. Rust
+/// monomorphizes generic functions (and any `static` local to them) per
+/// concrete type parameter, so `CountingPermutation<KeccakF>` and
+/// `CountingPermutation<KeccakF12>` each get their own independent
+/// counter; sharing a `CountingPermutation<P>` for the same `P` across
+/// concurrent tests still races on that one counter, same caveat as the
+/// ad hoc counting permutation in `permutation_count_tests` below.
+#[cfg(test)]
+pub(crate) struct CountingPermutation<P>(core::marker::PhantomData<P>);
+
+#[cfg(test)]
+impl<P: Permutation> CountingPermutation<P> {
+    fn counter() -> &'static core::sync::atomic::AtomicUsize {
+        static COUNT: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+        &COUNT
+    }
+
+    /// The number of times [`execute`](Permutation::execute) has been
+    /// called for this `P` since the last [`reset_count`](Self::reset_count).
+    pub(crate) fn count() -> usize {
+        Self::counter().load(core::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Zeroes the counter, so a fresh measurement doesn't include earlier
+    /// calls.
+    pub(crate) fn reset_count() {
+        Self::counter().store(0, core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+impl<P: Permutation> Permutation for CountingPermutation<P> {
+    type Lane = P::Lane;
+
+    fn execute(a: &mut Buffer<Self::Lane>) {
+        Self::counter().fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+        P::execute(a);
+    }
+}
+
+/// Which half of the absorb-then-squeeze lifecycle a [`KeccakState`] is in.
+///
+/// This only guards an internal invariant (see [`KeccakState::update`]):
+/// every public [`Hasher`] impl already makes absorbing-after-finalizing
+/// impossible at the type level, since `finalize` consumes `self` by value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Absorbing,
+    Squeezing,
+}
+
+/// The [`KeccakState::set_trace`] callback's boxed type, pulled out to its
+/// own alias so the `trace` field below doesn't spell out the
+/// `Box<dyn FnMut(..)>` inline (which is what trips clippy's
+/// `type_complexity` lint on a bare struct field).
+#[cfg(all(feature = "trace", feature = "alloc"))]
+type TraceCallback = alloc::boxed::Box<dyn FnMut(&[u64; 25])>;
+
+/// The generic sponge state (absorb buffer + rate/offset/domain-separation
+/// bookkeeping) shared by [`Keccak`], [`Sha3`] and [`Shake`].
+// `Clone` is derived except under `trace` + `alloc`, where the boxed
+// callback field below forces a manual impl instead (see it further down)
+// since `Box<dyn FnMut(..)>` isn't itself `Clone`.
+#[cfg_attr(not(all(feature = "trace", feature = "alloc")), derive(Clone))]
+struct KeccakState<P: Permutation> {
+    buffer: Buffer<P::Lane>,
+    offset: usize,
+    rate: usize,
+    delim: u8,
+    phase: Phase,
+    permutation: core::marker::PhantomData<P>,
+    // Running total of bytes passed to `update`, independent of `offset`
+    // (which wraps every rate block), for callers that need to know how
+    // much has been absorbed so far, e.g. to append a length suffix.
+    bytes_absorbed: u64,
+    // The `set_trace` callback, if one has been registered. Deliberately
+    // dropped (reset to `None`) on `Clone` rather than shared or
+    // duplicated — see the manual `Clone` impl below.
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    trace: Option<TraceCallback>,
+}
+
+/// Manual `Clone` impl, needed only because the boxed `trace` callback
+/// field isn't `Clone`; every other field is copied as usual and the
+/// callback itself is simply not carried over to the clone.
+#[cfg(all(feature = "trace", feature = "alloc"))]
+impl<P: Permutation> Clone for KeccakState<P> {
+    fn clone(&self) -> Self {
+        KeccakState {
+            buffer: self.buffer.clone(),
+            offset: self.offset,
+            rate: self.rate,
+            delim: self.delim,
+            phase: self.phase,
+            permutation: core::marker::PhantomData,
+            bytes_absorbed: self.bytes_absorbed,
+            trace: None,
+        }
+    }
+}
+
+/// Prints only `rate` and `delim` — never `buffer` or `offset`, since the
+/// buffer can hold secret or otherwise sensitive absorbed input (a
+/// KMAC/HMAC key, an unlogged message) that a `#[derive(Debug)]` would
+/// otherwise leak. See [`impl_debug_via_state`] for the macro every
+/// concrete [`Hasher`] wrapping a bare `state` field is given, built on
+/// this impl.
+impl<P: Permutation> core::fmt::Debug for KeccakState<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeccakState")
+            .field("rate", &self.rate)
+            .field("delim", &self.delim)
+            .finish()
+    }
+}
+
+impl<P: Permutation> KeccakState<P> {
+    /// Creates a sponge state with the given `rate` (in bytes) and
+    /// domain-separation suffix `delim`. `delim`'s bits, read from the LSB,
+    /// are the suffix appended by `pad10*1` after the message; the rule's
+    /// own terminating `1` bit is added separately by `finalize`/`pad`.
+    /// `Keccak::custom` exposes this on the public `Keccak` API.
+    ///
+    /// Not a `const fn`: `Buffer::default()` goes through `P::Lane`'s
+    /// generic `Default` bound, and calling a trait method from a const
+    /// context requires the unstable `const_trait_impl` feature. This (and
+    /// therefore every concrete constructor built on it, like
+    /// `Keccak::v256`) can only become `const` once that stabilizes.
+    fn new(rate: usize, delim: u8) -> Self {
+        assert!(rate != 0, "rate cannot be equal 0");
+        debug_assert!(
+            rate.is_multiple_of(core::mem::size_of::<P::Lane>()),
+            "rate must be a whole number of lanes; a rate that splits a lane \
+             would silently misalign the absorb/squeeze offset math below",
+        );
+        KeccakState {
+            buffer: Buffer::default(),
+            offset: 0,
+            rate,
+            delim,
+            phase: Phase::Absorbing,
+            permutation: core::marker::PhantomData,
+            bytes_absorbed: 0,
+            #[cfg(all(feature = "trace", feature = "alloc"))]
+            trace: None,
+        }
+    }
+
+    fn keccak(&mut self) {
+        P::execute(&mut self.buffer);
+        #[cfg(all(feature = "trace", feature = "alloc"))]
+        if let Some(lanes) = P::trace_lanes(&self.buffer) {
+            if let Some(trace) = self.trace.as_mut() {
+                trace(&lanes);
+            }
+        }
+    }
+
+    /// Registers a callback invoked with this sponge's full lane state, as
+    /// 25 little-endian 64-bit words, immediately after every permutation
+    /// performed during absorb or squeeze — e.g. to compare against
+    /// another Keccak implementation's intermediate state while tracking
+    /// down a `keccak256` mismatch. Only fires for permutations whose
+    /// lanes are 64 bits wide ([`Permutation::trace_lanes`]); on
+    /// narrower-lane permutations like [`KeccakP200`](crate::KeccakP200)
+    /// the callback is simply never invoked.
+    #[cfg(all(feature = "trace", feature = "alloc"))]
+    fn set_trace(&mut self, f: impl FnMut(&[u64; 25]) + 'static) {
+        self.trace = Some(alloc::boxed::Box::new(f));
+    }
+
+    /// Absorbs `input`.
+    ///
+    /// # Panics (debug builds only)
+    ///
+    /// Debug-panics if called after [`pad`](Self::pad) has switched this
+    /// state into squeezing: every public [`Hasher`] already prevents this
+    /// by consuming `self` in `finalize`, so this only catches a bug in
+    /// this crate's own internal plumbing (e.g. a future construction that
+    /// reuses a `KeccakState` past its `pad()` call) rather than user
+    /// misuse.
+    fn update(&mut self, input: &[u8]) {
+        debug_assert!(
+            self.phase == Phase::Absorbing,
+            "cannot absorb more input after squeezing has begun",
+        );
+        let rate = self.rate;
+        self.bytes_absorbed += input.len() as u64;
+
+        // Fast path for the common many-small-updates workload: when the
+        // whole slice fits with room to spare in the current rate block,
+        // one XOR and an offset bump is the entire absorb — no permutation,
+        // and none of the multi-block loop's per-iteration bookkeeping
+        // below. Strictly less than `rate` (not `<=`) so that exactly
+        // filling a block still falls through and permutes immediately,
+        // matching the general path's boundary behavior.
+        if self.offset + input.len() < rate {
+            self.buffer.xorin(input, self.offset, input.len());
+            self.offset += input.len();
+            return;
+        }
+
+        // Past the fast path above, `self.offset + input.len() >= rate`, so
+        // topping off the current block (if partially filled) always
+        // reaches exactly `rate` and triggers a permutation.
+        let mut input = input;
+        if self.offset != 0 {
+            let head_len = rate - self.offset;
+            self.buffer.xorin(&input[..head_len], self.offset, head_len);
+            input = &input[head_len..];
+            self.keccak();
+            self.offset = 0;
+        }
+
+        while input.len() >= rate {
+            self.buffer.xorin(&input[..rate], 0, rate);
+            self.keccak();
+            input = &input[rate..];
+        }
+
+        if !input.is_empty() {
+            self.buffer.xorin(input, 0, input.len());
+            self.offset = input.len();
+        }
+    }
+
+    /// Absorbs a compile-time-sized `data`, behaviorally identical to
+    /// `update(data)` but with `N` known at the call site, which lets the
+    /// optimizer elide the general absorb loop's bounds checks for callers
+    /// hashing fixed-size structs.
+    fn update_fixed<const N: usize>(&mut self, data: &[u8; N]) {
+        self.update(data);
+    }
+
+    /// Finalizes `other` into a stack buffer sized by its
+    /// [`Hasher::OUTPUT_LEN`] and absorbs the result, for hash-of-hash and
+    /// commitment-chain constructions (`H(H(a) || H(b))`) that would
+    /// otherwise need to size and allocate a temporary buffer by hand.
+    ///
+    /// Only meaningful for `H` whose `OUTPUT_LEN` is a real, non-zero
+    /// per-type constant (e.g. [`HmacSha3_256`](crate::HmacSha3_256)):
+    /// per [`Hasher::OUTPUT_LEN`]'s own documentation, types spanning
+    /// several security levels chosen at construction (`Sha3`, `Keccak`,
+    /// `Shake`, ...) leave it at the default `0`, since there is no single
+    /// correct digest length to size a buffer with. Panics if
+    /// `H::OUTPUT_LEN` is `0` or exceeds this crate's largest digest size
+    /// (64 bytes, `SHA3-512`/`Keccak-512`); callers hashing one of the
+    /// multi-security-level types should finalize into an explicitly sized
+    /// buffer and call `update` directly instead.
+    fn update_digest<H: Hasher>(&mut self, other: H) {
+        assert!(H::OUTPUT_LEN != 0, "update_digest requires a Hasher with a non-zero OUTPUT_LEN");
+        assert!(H::OUTPUT_LEN <= 64, "update_digest's stack buffer only covers up to a 64-byte digest");
+        let mut digest = [0u8; 64];
+        let digest = &mut digest[..H::OUTPUT_LEN];
+        other.finalize(digest);
+        self.update(digest);
+    }
+
+    /// Absorbs `words` directly into the rate lanes, XORing each 64-bit
+    /// word in exactly where the equivalent `update(&word.to_le_bytes())`
+    /// call would land and permuting on the same rate-boundary crossings,
+    /// but without repacking through a byte buffer first. Only available
+    /// on 64-bit-lane permutations (`f[1600]`), since that's the only lane
+    /// width where "a word" and "a lane" coincide.
+    ///
+    /// # Panics (debug builds only)
+    ///
+    /// Debug-panics if `rate` is not a multiple of 8 bytes: every standard
+    /// `f[1600]` configuration in this crate satisfies this, but a custom
+    /// rate constructed via [`Keccak::custom`](crate::Keccak::custom) with
+    /// an unusual `bits` value might not.
+    fn update_words(&mut self, words: &[u64])
+    where
+        P: Permutation<Lane = u64>,
+    {
+        debug_assert!(
+            self.phase == Phase::Absorbing,
+            "cannot absorb more input after squeezing has begun",
+        );
+        debug_assert_eq!(self.rate % 8, 0, "update_words requires a word-sized rate");
+
+        if words.is_empty() {
+            return;
+        }
+
+        // A prior byte-wise `update()` may have left `self.offset` short of
+        // a lane boundary. Catching up by absorbing whole words the
+        // ordinary way can never fix that: every word is 8 bytes, and
+        // `rate` is always a multiple of 8, so `offset % 8` is invariant
+        // under absorbing any whole number of them, rate-boundary
+        // permutations included. Instead, absorb only the `shift` bytes
+        // needed to reach the next lane boundary, then reconstruct the
+        // rest of `words` as new lanes shifted by that same amount — each
+        // built from the tail of one input word and the head of the
+        // next — so every lane still lands exactly where
+        // `update(&word.to_le_bytes())` would have put it.
+        let shift = self.offset % 8;
+        if shift == 0 {
+            for &word in words {
+                let lane = self.offset / 8;
+                self.buffer.words()[lane] ^= word.to_le();
+                self.offset += 8;
+                if self.offset == self.rate {
+                    self.keccak();
+                    self.offset = 0;
+                }
+            }
+            return;
+        }
+
+        let needed = 8 - shift;
+        let first = words[0].to_le_bytes();
+        self.update(&first[..needed]);
+
+        for pair in words.windows(2) {
+            let tail = pair[0].to_le() >> (8 * needed);
+            let head = pair[1].to_le() << (8 * shift);
+            let lane = self.offset / 8;
+            self.buffer.words()[lane] ^= tail | head;
+            self.offset += 8;
+            if self.offset == self.rate {
+                self.keccak();
+                self.offset = 0;
+            }
+        }
+
+        let last = words[words.len() - 1].to_le_bytes();
+        self.update(&last[needed..]);
+    }
+
+    fn pad(&mut self) {
+        self.buffer.pad(self.offset, self.delim, self.rate);
+        self.phase = Phase::Squeezing;
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        let rate = self.rate;
+        let mut output = output;
+
+        while output.len() >= rate {
+            self.buffer.setout(&mut output[..rate], 0, rate);
+            self.keccak();
+            output = &mut output[rate..];
+        }
+
+        let len = output.len();
+        self.buffer.setout(output, 0, len);
+    }
+
+    fn finalize(mut self, output: &mut [u8]) {
+        self.pad();
+        self.keccak();
+        self.squeeze(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, preserving
+    /// `rate` and `delim`, so the state can be reused for a fresh message.
+    fn reset(&mut self) {
+        self.buffer = Buffer::default();
+        self.offset = 0;
+        self.phase = Phase::Absorbing;
+        self.bytes_absorbed = 0;
+    }
+
+    /// Squeezes into `output` and then resets, without consuming `self`.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.pad();
+        self.keccak();
+        self.squeeze(output);
+        self.reset();
+    }
+
+    /// The sponge rate, in bytes, this state was constructed with.
+    fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// The total number of bytes passed to [`update`](Self::update) since
+    /// construction or the last [`reset`](Self::reset)/
+    /// [`finalize_reset`](Self::finalize_reset).
+    fn bytes_absorbed(&self) -> u64 {
+        self.bytes_absorbed
+    }
+
+    /// Compares two sponge states field-by-field, including the full
+    /// absorb buffer, for a test that wants to assert two hashers are in an
+    /// identical state (e.g. after being fed the same bytes through
+    /// different chunkings) without finalizing either one and comparing
+    /// digests instead. Test-only, not `pub`: [`Hasher::finalize`] already
+    /// gives every ordinary caller a cheaper way to check two hashers
+    /// agree, at the cost of consuming them.
+    #[cfg(test)]
+    fn state_eq(&self, other: &Self) -> bool {
+        self.rate == other.rate
+            && self.delim == other.delim
+            && self.offset == other.offset
+            && self.phase == other.phase
+            && self.buffer.clone().bytes_eq(&mut other.buffer.clone())
+    }
+
+    /// The sponge capacity, in bits, this state was constructed with:
+    /// the permutation's full state width minus the rate.
+    fn capacity_bits(&self) -> usize {
+        let state_bits = core::mem::size_of::<P::Lane>() * 25 * 8;
+        state_bits - self.rate * 8
+    }
+
+    /// Returns the raw sponge bytes, independent of `P::Lane`'s width, for
+    /// snapshotting via `serde`.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn raw_bytes(&self) -> std::vec::Vec<u8> {
+        let bytes = core::mem::size_of::<P::Lane>() * 25;
+        let mut buffer = self.buffer.clone();
+        let mut out = std::vec![0u8; bytes];
+        buffer.execute(0, bytes, |b| out.copy_from_slice(b));
+        out
+    }
+
+    /// Rebuilds a sponge state from raw bytes previously produced by
+    /// [`raw_bytes`](Self::raw_bytes), rejecting a `raw`/`rate`/`offset`
+    /// combination that couldn't have come from a real sponge instead of
+    /// trusting them and risking a panic or garbage output later.
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn from_raw_parts(raw: &[u8], offset: usize, rate: usize, delim: u8) -> Result<Self, &'static str> {
+        let expected = core::mem::size_of::<P::Lane>() * 25;
+        if raw.len() != expected {
+            return Err("buffer length does not match the permutation's lane width");
+        }
+        if rate == 0 || rate > expected {
+            return Err("rate is inconsistent with the permutation's state size");
+        }
+        if offset >= rate {
+            return Err("offset must be less than rate");
+        }
+        let mut buffer = Buffer::default();
+        buffer.execute(0, expected, |b| b.copy_from_slice(raw));
+        Ok(KeccakState {
+            buffer,
+            offset,
+            rate,
+            delim,
+            phase: Phase::Absorbing,
+            permutation: core::marker::PhantomData,
+            // The raw bytes carry no absorbed-length history, so this
+            // starts the count over rather than guessing.
+            bytes_absorbed: 0,
+            #[cfg(all(feature = "trace", feature = "alloc"))]
+            trace: None,
+        })
+    }
+}
+
+impl<P: Permutation<Lane = u64>> KeccakState<P> {
+    /// Rebuilds a sponge state from a raw 1600-bit Keccak state (25
+    /// 64-bit lanes) computed elsewhere — e.g. a precomputed IV, or a
+    /// state captured by a different implementation — so
+    /// absorbing/squeezing can continue from it. The inverse of
+    /// [`into_raw`](Self::into_raw).
+    ///
+    /// Unlike [`from_raw_parts`](Self::from_raw_parts) (behind the
+    /// `serde` feature), this takes the `[u64; 25]` lane array most other
+    /// Keccak implementations use as their canonical raw-state
+    /// representation directly, rather than staging through a byte `Vec`,
+    /// so it needs neither `serde` nor `std`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or exceeds the permutation's full byte
+    /// width (`200` for `f[1600]`), or if `offset >= rate` (an offset can
+    /// only describe a partial block that hasn't yet reached a full
+    /// rate's worth of bytes).
+    fn from_raw(state: [u64; 25], rate: usize, delim: u8, offset: usize) -> Self {
+        let full_width = core::mem::size_of::<u64>() * 25;
+        assert!(
+            rate != 0 && rate <= full_width,
+            "rate is inconsistent with the permutation's state size"
+        );
+        assert!(offset < rate, "offset must be less than rate");
+        KeccakState {
+            buffer: Buffer(state),
+            offset,
+            rate,
+            delim,
+            phase: Phase::Absorbing,
+            permutation: core::marker::PhantomData,
+            // The raw lanes carry no absorbed-length history, so this
+            // starts the count over rather than guessing.
+            bytes_absorbed: 0,
+            #[cfg(all(feature = "trace", feature = "alloc"))]
+            trace: None,
+        }
+    }
+
+    /// Tears this state down into its raw 1600-bit lane array and absorb
+    /// offset, the inverse of [`from_raw`](Self::from_raw), for handing
+    /// off to external tooling or debuggers that want the sponge's
+    /// internal state directly.
+    fn into_raw(self) -> ([u64; 25], usize) {
+        (self.buffer.0, self.offset)
+    }
+
+    /// Returns the raw 1600-bit state as 25 little-endian 64-bit lanes —
+    /// the same lane values [`into_raw`](Self::into_raw) and
+    /// [`from_raw`](Self::from_raw) use, and the byte order the Keccak
+    /// specification itself defines lanes in.
+    fn raw_state_le(&self) -> [u64; 25] {
+        self.buffer.0
+    }
+
+    /// Returns the raw 1600-bit state as 25 big-endian 64-bit lanes, i.e.
+    /// each of [`raw_state_le`](Self::raw_state_le)'s lanes with its bytes
+    /// reversed. Round-trip through [`from_raw`](Self::from_raw) by
+    /// byte-swapping back to little-endian first — `from_raw` always takes
+    /// little-endian lanes.
+    fn raw_state_be(&self) -> [u64; 25] {
+        let mut lanes = self.buffer.0;
+        for lane in &mut lanes {
+            *lane = lane.swap_bytes();
+        }
+        lanes
+    }
+}
+
+/// Zeroes the sponge buffer when a [`KeccakState`] is dropped, so secret key
+/// material absorbed by keyed constructions ([`Kmac128`](crate::Kmac128),
+/// [`Kmac256`](crate::Kmac256), and anything else built on `KeccakState`)
+/// doesn't linger in memory. The keyed wrappers themselves need no separate
+/// `Drop` impl: dropping them drops their inner `KeccakState` field, which
+/// runs this.
+///
+/// Without the `zeroize` feature this impl doesn't exist at all, so the
+/// default build pays no cost for it.
+#[cfg(feature = "zeroize")]
+impl<P: Permutation> Drop for KeccakState<P> {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod phase_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    #[should_panic(expected = "cannot absorb more input after squeezing has begun")]
+    fn absorbing_after_pad_debug_panics() {
+        let mut state: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        state.update(b"hello");
+        state.pad();
+        state.update(b"more");
+    }
+
+    #[test]
+    fn legitimate_absorb_then_squeeze_still_succeeds() {
+        let mut state: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        state.update(b"hello");
+        let mut output = [0u8; 32];
+        state.finalize(&mut output);
+        assert_ne!(output, [0u8; 32]);
+    }
+
+    #[test]
+    fn reset_returns_to_the_absorbing_phase() {
+        let mut state: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        state.update(b"hello");
+        let mut output = [0u8; 32];
+        state.finalize_reset(&mut output);
+        // Having reset back to Absorbing, further updates must not panic.
+        state.update(b"another message");
+    }
+
+    #[test]
+    fn bytes_absorbed_sums_update_lengths_and_reset_zeroes_it() {
+        let mut state: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        assert_eq!(state.bytes_absorbed(), 0);
+
+        state.update(b"hello");
+        state.update(b"world!!");
+        state.update(&[0u8; 200]);
+        assert_eq!(state.bytes_absorbed(), 5 + 7 + 200);
+
+        let mut output = [0u8; 32];
+        state.finalize_reset(&mut output);
+        assert_eq!(state.bytes_absorbed(), 0);
+    }
+}
+
+#[cfg(test)]
+mod permutation_count_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A `Permutation` that counts how many times it's invoked and otherwise
+    /// just delegates to `KeccakF`, for asserting on the number of
+    /// `keccak-f[1600]` calls a given absorb/finalize sequence performs
+    /// without having to instrument `KeccakState` itself.
+    struct CountingKeccakF;
+
+    static PERMUTE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    impl Permutation for CountingKeccakF {
+        type Lane = u64;
+
+        fn execute(a: &mut Buffer<u64>) {
+            PERMUTE_COUNT.fetch_add(1, Ordering::SeqCst);
+            KeccakF::execute(a);
+        }
+    }
+
+    /// Runs `message` through a fresh `CountingKeccakF` state and returns
+    /// the number of permutations it performed, absorbing and finalizing
+    /// exactly as any real `Hasher` would.
+    ///
+    /// `PERMUTE_COUNT` is a single global counter, so callers within the
+    /// same test binary must not run concurrently with each other -- kept
+    /// as one `#[test]` function below rather than several, to avoid a
+    /// cross-test race under the default parallel test runner.
+    fn count_permutations(rate: usize, message: &[u8]) -> usize {
+        PERMUTE_COUNT.store(0, Ordering::SeqCst);
+        let mut state: KeccakState<CountingKeccakF> = KeccakState::new(rate, 0x06);
+        state.update(message);
+        let mut output = [0u8; 32];
+        state.finalize(&mut output);
+        PERMUTE_COUNT.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn absorption_permutes_once_per_full_block_with_no_extra_at_the_boundary() {
+        // SHA3-256's rate is 136 bytes. Every length permutes
+        // `len / rate` times while absorbing plus exactly one more for
+        // padding at finalize -- an exact multiple of the rate must not
+        // trigger an extra permutation eagerly during absorption, and the
+        // trailing partial block (if any) rides along with that same
+        // finalize permutation rather than needing one of its own.
+        assert_eq!(count_permutations(136, &[]), 1, "empty input: one padding permutation");
+
+        let one_rate_minus_one = [0x5au8; 136 - 1];
+        assert_eq!(count_permutations(136, &one_rate_minus_one), 1);
+
+        let one_rate = [0x5au8; 136];
+        assert_eq!(
+            count_permutations(136, &one_rate),
+            2,
+            "an exact multiple of the rate: one absorbing permutation, no extra one at the boundary",
+        );
+
+        let two_rate_minus_one = [0x5au8; 136 * 2 - 1];
+        assert_eq!(count_permutations(136, &two_rate_minus_one), 2);
+
+        let two_rate = [0x5au8; 136 * 2];
+        assert_eq!(
+            count_permutations(136, &two_rate),
+            3,
+            "two exact blocks: two absorbing permutations plus one for padding",
+        );
+
+        let two_rate_plus_one = [0x5au8; 136 * 2 + 1];
+        assert_eq!(
+            count_permutations(136, &two_rate_plus_one),
+            3,
+            "the trailing byte joins the padding permutation rather than needing its own",
+        );
+    }
+}
+
+#[cfg(test)]
+mod counting_permutation_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    fn a_two_block_message_permutes_exactly_three_times() {
+        CountingPermutation::<KeccakF>::reset_count();
+
+        // SHA3-256's rate is 136 bytes: two full rate blocks permute once
+        // each while absorbing, plus one more for padding at finalize.
+        let mut state: KeccakState<CountingPermutation<KeccakF>> =
+            KeccakState::new(136, 0x06);
+        state.update(&[0x5au8; 136 * 2]);
+        let mut output = [0u8; 32];
+        state.finalize(&mut output);
+
+        assert_eq!(CountingPermutation::<KeccakF>::count(), 3);
+    }
+}
+
+#[cfg(test)]
+mod update_words_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    fn update_words_matches_update_of_the_equivalent_le_bytes() {
+        let words: [u64; 20] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x0102_0304_0506_0708));
+
+        // Rate 136 bytes (SHA3-256) = 17 words, so 20 words crosses one
+        // rate boundary partway through the input.
+        let mut via_words: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        via_words.update_words(&words);
+        let mut via_words_out = [0u8; 32];
+        via_words.finalize(&mut via_words_out);
+
+        let mut via_bytes: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        for word in &words {
+            via_bytes.update(&word.to_le_bytes());
+        }
+        let mut via_bytes_out = [0u8; 32];
+        via_bytes.finalize(&mut via_bytes_out);
+
+        assert_eq!(via_words_out, via_bytes_out);
+    }
+
+    #[test]
+    fn update_words_after_a_partial_byte_update_still_matches() {
+        // Exercises the word-offset-not-aligned fallback path: three bytes
+        // are absorbed first, leaving `offset == 3`, before switching to
+        // update_words.
+        let words: [u64; 5] = [1, 2, 3, 4, 5];
+
+        let mut via_words: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        via_words.update(b"abc");
+        via_words.update_words(&words);
+        let mut via_words_out = [0u8; 32];
+        via_words.finalize(&mut via_words_out);
+
+        let mut via_bytes: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        via_bytes.update(b"abc");
+        for word in &words {
+            via_bytes.update(&word.to_le_bytes());
+        }
+        let mut via_bytes_out = [0u8; 32];
+        via_bytes.finalize(&mut via_bytes_out);
+
+        assert_eq!(via_words_out, via_bytes_out);
+    }
+
+    #[test]
+    fn update_words_matches_at_every_possible_partial_byte_offset() {
+        // `offset % 8` after the leading `update` ranges over every value in
+        // 0..8 as `prefix_len` sweeps 0..8, exercising every `shift` the
+        // realignment logic in `update_words` can see, including the
+        // already-aligned `shift == 0` case.
+        let words: [u64; 21] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x1122_3344_5566_7788));
+
+        for prefix_len in 0..8usize {
+            let prefix = b"the quick brown fox"[..prefix_len].to_vec();
+
+            let mut via_words: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+            via_words.update(&prefix);
+            via_words.update_words(&words);
+            let mut via_words_out = [0u8; 32];
+            via_words.finalize(&mut via_words_out);
+
+            let mut via_bytes: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+            via_bytes.update(&prefix);
+            for word in &words {
+                via_bytes.update(&word.to_le_bytes());
+            }
+            let mut via_bytes_out = [0u8; 32];
+            via_bytes.finalize(&mut via_bytes_out);
+
+            assert_eq!(via_words_out, via_bytes_out, "mismatch at prefix_len = {prefix_len}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod update_fixed_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    fn update_fixed_matches_update_of_the_equivalent_slice() {
+        let data: [u8; 64] = core::array::from_fn(|i| i as u8);
+
+        let mut via_fixed: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        via_fixed.update_fixed(&data);
+        let mut via_fixed_out = [0u8; 32];
+        via_fixed.finalize(&mut via_fixed_out);
+
+        let mut via_slice: KeccakState<KeccakF> = KeccakState::new(136, 0x06);
+        via_slice.update(&data[..]);
+        let mut via_slice_out = [0u8; 32];
+        via_slice.finalize(&mut via_slice_out);
+
+        assert_eq!(via_fixed_out, via_slice_out);
+    }
+}
+
+// `update_digest` requires `H::OUTPUT_LEN != 0`; `Sha3`/`Keccak`/`Shake`
+// intentionally leave it at the default `0` (see `Hasher::OUTPUT_LEN`'s own
+// docs), so `HmacSha3_256` — the one `Hasher` impl in this crate with a
+// real fixed `OUTPUT_LEN` — stands in for the "hash of a hash" example.
+#[cfg(all(test, feature = "keccak", feature = "hmac", feature = "sha3", feature = "std"))]
+mod update_digest_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+    use crate::HmacSha3_256;
+
+    #[test]
+    fn update_digest_matches_manually_finalizing_and_updating() {
+        let inner = HmacSha3_256::new(b"key").chain(b"hello");
+
+        let mut via_update_digest: KeccakState<KeccakF> = KeccakState::new(136, 0x01);
+        via_update_digest.update_digest(inner.clone());
+        let mut via_update_digest_out = [0u8; 32];
+        via_update_digest.finalize(&mut via_update_digest_out);
+
+        let mut manual_digest = [0u8; HmacSha3_256::OUTPUT_LEN];
+        inner.finalize(&mut manual_digest);
+        let mut via_manual: KeccakState<KeccakF> = KeccakState::new(136, 0x01);
+        via_manual.update(&manual_digest);
+        let mut via_manual_out = [0u8; 32];
+        via_manual.finalize(&mut via_manual_out);
+
+        assert_eq!(via_update_digest_out, via_manual_out);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero OUTPUT_LEN")]
+    fn update_digest_panics_for_a_hasher_without_a_fixed_output_len() {
+        // `Keccak`'s `OUTPUT_LEN` is the crate-wide default `0`, since it
+        // spans several security levels chosen at construction.
+        let mut state: KeccakState<KeccakF> = KeccakState::new(136, 0x01);
+        state.update_digest(crate::Keccak::v256().chain(b"hello"));
+    }
+}
+
+#[cfg(all(test, feature = "keccak"))]
+mod update_iter_tests {
+    use super::*;
+    use crate::Keccak;
+
+    #[test]
+    fn update_iter_matches_update_across_several_rate_blocks() {
+        // SHA3/Keccak-256's rate is 136 bytes, so 500 bytes spans several
+        // rate-sized blocks plus a partial one.
+        let data: [u8; 500] = core::array::from_fn(|i| i as u8);
+
+        let mut via_iter = Keccak::v256();
+        via_iter.update_iter(data.iter().copied());
+        let mut via_iter_out = [0u8; 32];
+        via_iter.finalize(&mut via_iter_out);
+
+        let mut via_slice = Keccak::v256();
+        via_slice.update(&data);
+        let mut via_slice_out = [0u8; 32];
+        via_slice.finalize(&mut via_slice_out);
+
+        assert_eq!(via_iter_out, via_slice_out);
+    }
+}
+
+#[cfg(all(test, feature = "keccak"))]
+mod update_vectored_tests {
+    use super::*;
+    use crate::Keccak;
+
+    #[test]
+    fn update_vectored_matches_sequential_updates_and_a_concatenated_buffer() {
+        // Choose slice lengths that don't line up with Keccak-256's
+        // 136-byte rate, so at least one rate block straddles a slice
+        // boundary.
+        let a = [0x11u8; 50];
+        let b = [0x22u8; 90];
+        let c = [0x33u8; 200];
+
+        let mut via_vectored = Keccak::v256();
+        via_vectored.update_vectored(&[&a, &b, &c]);
+        let mut via_vectored_out = [0u8; 32];
+        via_vectored.finalize(&mut via_vectored_out);
+
+        let mut via_sequential = Keccak::v256();
+        via_sequential.update(&a);
+        via_sequential.update(&b);
+        via_sequential.update(&c);
+        let mut via_sequential_out = [0u8; 32];
+        via_sequential.finalize(&mut via_sequential_out);
+
+        let mut concatenated = [0u8; 50 + 90 + 200];
+        concatenated[..50].copy_from_slice(&a);
+        concatenated[50..140].copy_from_slice(&b);
+        concatenated[140..].copy_from_slice(&c);
+        let mut via_concat = Keccak::v256();
+        via_concat.update(&concatenated);
+        let mut via_concat_out = [0u8; 32];
+        via_concat.finalize(&mut via_concat_out);
+
+        assert_eq!(via_vectored_out, via_sequential_out);
+        assert_eq!(via_vectored_out, via_concat_out);
+    }
+}
+
+#[cfg(all(test, feature = "keccak"))]
+mod update_byte_tests {
+    use super::*;
+    use crate::Keccak;
+
+    #[test]
+    fn a_sequence_of_update_byte_calls_matches_update_of_the_collected_bytes() {
+        // Keccak-256's rate is 136 bytes, so 300 one-byte updates cross
+        // more than one rate boundary mid-sequence.
+        let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+
+        let mut via_byte = Keccak::v256();
+        for &byte in &data {
+            via_byte.update_byte(byte);
+        }
+        let mut via_byte_out = [0u8; 32];
+        via_byte.finalize(&mut via_byte_out);
+
+        let mut via_bulk = Keccak::v256();
+        via_bulk.update(&data);
+        let mut via_bulk_out = [0u8; 32];
+        via_bulk.finalize(&mut via_bulk_out);
+
+        assert_eq!(via_byte_out, via_bulk_out);
+    }
+
+    #[test]
+    fn update_byte_interoperates_with_a_following_bulk_update() {
+        let mut mixed = Keccak::v256();
+        mixed.update_byte(0x11);
+        mixed.update_byte(0x22);
+        mixed.update(&[0x33, 0x44]);
+        let mut mixed_out = [0u8; 32];
+        mixed.finalize(&mut mixed_out);
+
+        let mut bulk = Keccak::v256();
+        bulk.update(&[0x11, 0x22, 0x33, 0x44]);
+        let mut bulk_out = [0u8; 32];
+        bulk.finalize(&mut bulk_out);
+
+        assert_eq!(mixed_out, bulk_out);
+    }
+}
+
+#[cfg(all(test, feature = "keccak", feature = "sp800"))]
+mod derive_tests {
+    use super::*;
+    use crate::Keccak;
+
+    #[test]
+    fn distinct_labels_derive_distinct_digests_and_leave_the_parent_unchanged() {
+        let mut parent = Keccak::v256();
+        parent.update(b"shared context");
+
+        let mut derived_a = parent.derive(b"child-a");
+        derived_a.update(b"payload");
+        let mut out_a = [0u8; 32];
+        derived_a.finalize(&mut out_a);
+
+        let mut derived_b = parent.derive(b"child-b");
+        derived_b.update(b"payload");
+        let mut out_b = [0u8; 32];
+        derived_b.finalize(&mut out_b);
+
+        assert_ne!(out_a, out_b, "distinct labels must diverge");
+
+        // The parent itself must be untouched by either `derive` call.
+        let mut fresh_parent = Keccak::v256();
+        fresh_parent.update(b"shared context");
+        let mut want_parent_out = [0u8; 32];
+        let mut got_parent_out = [0u8; 32];
+        fresh_parent.finalize(&mut want_parent_out);
+        parent.finalize(&mut got_parent_out);
+        assert_eq!(want_parent_out, got_parent_out);
+    }
+
+    #[test]
+    fn deriving_a_label_differs_from_updating_it_directly() {
+        // `derive(x).update(y)` absorbs `left_encode(|x|) || x || y`,
+        // which must differ from plain `update(x); update(y)` absorbing
+        // just `x || y` with no length prefix.
+        let mut via_derive = Keccak::v256().derive(b"label");
+        via_derive.update(b"payload");
+        let mut derive_out = [0u8; 32];
+        via_derive.finalize(&mut derive_out);
+
+        let mut via_update = Keccak::v256();
+        via_update.update(b"label");
+        via_update.update(b"payload");
+        let mut update_out = [0u8; 32];
+        via_update.finalize(&mut update_out);
+
+        assert_ne!(derive_out, update_out);
+    }
+}
+
+#[cfg(all(test, feature = "keccak", feature = "std"))]
+mod update_reader_tests {
+    use super::*;
+    use crate::Keccak;
+
+    /// A reader that never returns more than 3 bytes per call, to exercise
+    /// `update_reader`'s short-read loop rather than only the happy path
+    /// where one `read` drains the whole source.
+    struct ShortReadingReader<'a> {
+        remaining: &'a [u8],
+    }
+
+    impl<'a> std::io::Read for ShortReadingReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = 3.min(buf.len()).min(self.remaining.len());
+            buf[..n].copy_from_slice(&self.remaining[..n]);
+            self.remaining = &self.remaining[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn update_reader_from_a_cursor_matches_a_direct_update() {
+        let data: [u8; 500] = core::array::from_fn(|i| i as u8);
+
+        let mut via_reader = Keccak::v256();
+        let absorbed = via_reader
+            .update_reader(std::io::Cursor::new(&data[..]))
+            .unwrap();
+        let mut via_reader_out = [0u8; 32];
+        via_reader.finalize(&mut via_reader_out);
+
+        let mut via_slice = Keccak::v256();
+        via_slice.update(&data);
+        let mut via_slice_out = [0u8; 32];
+        via_slice.finalize(&mut via_slice_out);
+
+        assert_eq!(absorbed, data.len() as u64);
+        assert_eq!(via_reader_out, via_slice_out);
+    }
+
+    #[test]
+    fn update_reader_handles_short_reads() {
+        let data: [u8; 500] = core::array::from_fn(|i| i as u8);
+
+        let mut via_reader = Keccak::v256();
+        let absorbed = via_reader
+            .update_reader(ShortReadingReader { remaining: &data })
+            .unwrap();
+        let mut via_reader_out = [0u8; 32];
+        via_reader.finalize(&mut via_reader_out);
+
+        let mut via_slice = Keccak::v256();
+        via_slice.update(&data);
+        let mut via_slice_out = [0u8; 32];
+        via_slice.finalize(&mut via_slice_out);
+
+        assert_eq!(absorbed, data.len() as u64);
+        assert_eq!(via_reader_out, via_slice_out);
+    }
+}
+
+#[cfg(all(test, feature = "zeroize"))]
+mod zeroize_tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+
+    #[test]
+    fn drop_zeroes_the_sponge_buffer() {
+        // Best-effort: `ManuallyDrop` lets us run exactly the `Drop` impl
+        // (via `drop_in_place`) without also deallocating/reusing the
+        // backing memory, so the buffer's bytes can still be inspected
+        // afterwards through the same, still-valid, address. A plain
+        // `drop(state)` would move `state` into `drop`'s parameter first,
+        // relocating it, and a boxed value's memory gets reclaimed by the
+        // allocator (and overwritten with bookkeeping) as soon as it's
+        // freed -- neither leaves a stable, readable address to check.
+        let mut state = core::mem::ManuallyDrop::new(KeccakState::<KeccakF>::new(136, 0x06));
+        state.update(b"super secret key material, not just filler");
+        let ptr = &state.buffer as *const Buffer<u64> as *const u8;
+        let len = core::mem::size_of::<Buffer<u64>>();
+
+        unsafe { core::ptr::drop_in_place(&mut *state as *mut KeccakState<KeccakF>) };
+
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+}
+
+/// A resumable extendable-output reader shared by every XOF's public
+/// `finalize_xof`, parameterized over the permutation so it works for both
+/// the standard `f[1600]` XOFs and reduced-round ones like TurboSHAKE.
+///
+/// Squeezing `n` bytes across several `squeeze` calls of arbitrary sizes
+/// produces the same `n` bytes as squeezing them in one call: the reader
+/// tracks the partial-block offset and only re-permutes the sponge once a
+/// full rate's worth of output has been read.
+#[derive(Clone)]
+pub(crate) struct XofReader<P: Permutation> {
+    state: KeccakState<P>,
+    offset: usize,
+}
+
+impl<P: Permutation> XofReader<P> {
+    /// Pads and permutes the finalized `state`, then wraps it for squeezing.
+    fn new(mut state: KeccakState<P>) -> Self {
+        state.pad();
+        state.keccak();
+        XofReader { state, offset: 0 }
+    }
+
+    /// Squeezes `buf.len()` more bytes, continuing from wherever the
+    /// previous `squeeze` call (if any) left off.
+    fn squeeze(&mut self, mut buf: &mut [u8]) {
+        let rate = self.state.rate;
+        while !buf.is_empty() {
+            let take = core::cmp::min(rate - self.offset, buf.len());
+            let (head, tail) = buf.split_at_mut(take);
+            self.state.buffer.setout(head, self.offset, take);
+            self.offset += take;
+            buf = tail;
+            if self.offset == rate {
+                self.state.keccak();
+                self.offset = 0;
+            }
+        }
+    }
+
+    /// Advances the squeeze position by `n` bytes without materializing
+    /// them, permuting as needed along the way. Identical to squeezing `n`
+    /// bytes into a throwaway buffer, just without the copy.
+    fn skip(&mut self, mut n: usize) {
+        let rate = self.state.rate;
+        while n > 0 {
+            let take = core::cmp::min(rate - self.offset, n);
+            self.offset += take;
+            n -= take;
+            if self.offset == rate {
+                self.state.keccak();
+                self.offset = 0;
+            }
+        }
+    }
+
+    /// Squeezes `expected.len()` more bytes in fixed-size chunks, comparing
+    /// each against `expected` as it goes, and returns the index of the
+    /// first mismatching byte instead of just "they differ" — useful for
+    /// pinpointing where a reduced-round or SIMD implementation diverges
+    /// from the scalar one. Never materializes the whole stream at once.
+    fn verify_stream(&mut self, expected: &[u8]) -> Result<(), usize> {
+        const CHUNK: usize = 64;
+        let mut chunk = [0u8; CHUNK];
+        let mut pos = 0;
+        for want in expected.chunks(CHUNK) {
+            let got = &mut chunk[..want.len()];
+            self.squeeze(got);
+            if let Some(i) = got.iter().zip(want).position(|(g, w)| g != w) {
+                return Err(pos + i);
+            }
+            pos += want.len();
+        }
+        Ok(())
+    }
+}
+
+/// Deterministic, seedable property-style coverage of absorb/squeeze
+/// chunking across the crate's hasher types and XOF readers.
+///
+/// With no `Cargo.toml` to add `proptest`/`quickcheck` as a dev-dependency
+/// to, this hand-rolls a small seeded PRNG instead — the same shape already
+/// used for [`keccak::tests`](crate::keccak)'s big-input coverage — which
+/// gets the same deterministic-seed-for-CI-reproducibility property a real
+/// property-testing crate would, without needing one.
+#[cfg(all(test, feature = "keccak", feature = "sha3", feature = "shake", feature = "std"))]
+mod chunking_fuzz_tests {
+    use super::*;
+
+    struct SplitMix64(u64);
+
+    impl SplitMix64 {
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        /// A length in `1..=max` (or `0` if `max == 0`).
+        fn next_len(&mut self, max: usize) -> usize {
+            if max == 0 {
+                0
+            } else {
+                1 + (self.next_u64() as usize % max)
+            }
+        }
+    }
+
+    fn random_message(rng: &mut SplitMix64, len: usize) -> std::vec::Vec<u8> {
+        (0..len).map(|_| rng.next_u64() as u8).collect()
+    }
+
+    /// Absorbs `data` via arbitrarily many, randomly-sized `update` calls
+    /// and asserts the result matches absorbing it in one call, for a
+    /// handful of random messages and random chunkings.
+    fn assert_chunked_update_matches_one_shot<H: Hasher>(
+        rng: &mut SplitMix64,
+        mut fresh: impl FnMut() -> H,
+        output_len: usize,
+    ) {
+        for message_len in [0, 1, 135, 136, 137, 1000] {
+            let data = random_message(rng, message_len);
+
+            let mut want = std::vec![0u8; output_len];
+            fresh().chain(&data).finalize(&mut want);
+
+            for _ in 0..5 {
+                let mut hasher = fresh();
+                let mut remaining = &data[..];
+                while !remaining.is_empty() {
+                    let chunk_len = rng.next_len(remaining.len());
+                    let (chunk, rest) = remaining.split_at(chunk_len);
+                    hasher.update(chunk);
+                    remaining = rest;
+                }
+                let mut got = std::vec![0u8; output_len];
+                hasher.finalize(&mut got);
+                assert_eq!(got, want);
+            }
+        }
+    }
+
+    #[test]
+    fn keccak256_matches_across_random_chunkings() {
+        let mut rng = SplitMix64(0x0123_4567_89ab_cdef);
+        assert_chunked_update_matches_one_shot(&mut rng, crate::Keccak::v256, 32);
+    }
+
+    #[test]
+    fn sha3_256_matches_across_random_chunkings() {
+        let mut rng = SplitMix64(0x1122_3344_5566_7788);
+        assert_chunked_update_matches_one_shot(&mut rng, crate::Sha3::v256, 32);
+    }
+
+    #[test]
+    fn shake128_absorb_matches_across_random_chunkings() {
+        let mut rng = SplitMix64(0xdead_beef_cafe_f00d);
+        assert_chunked_update_matches_one_shot(&mut rng, crate::Shake::v128, 32);
+    }
+
+    #[test]
+    fn shake_xof_squeeze_matches_across_random_read_chunk_sizes() {
+        let mut rng = SplitMix64(0x2468_1357_9bdf_eca0);
+
+        for message_len in [0, 1, 168, 500] {
+            let data = random_message(&mut rng, message_len);
+
+            for &squeeze_len in &[0usize, 1, 168, 337, 1000] {
+                let mut want = std::vec![0u8; squeeze_len];
+                crate::Shake::v128().chain(&data).finalize_xof().squeeze(&mut want);
+
+                for _ in 0..5 {
+                    let mut reader = crate::Shake::v128().chain(&data).finalize_xof();
+                    let mut got = std::vec![0u8; squeeze_len];
+                    let mut remaining = &mut got[..];
+                    while !remaining.is_empty() {
+                        let chunk_len = rng.next_len(remaining.len());
+                        let (chunk, rest) = remaining.split_at_mut(chunk_len);
+                        reader.squeeze(chunk);
+                        remaining = rest;
+                    }
+                    assert_eq!(got, want);
+                }
+            }
+        }
+    }
+
+    /// Targets `KeccakState::update`'s single-block fast path specifically
+    /// (as opposed to [`assert_chunked_update_matches_one_shot`]'s broader
+    /// chunk sizes, which mostly exercise the general multi-block loop): a
+    /// long run of 1-4 byte updates never lets a chunk cross more than one
+    /// rate-block boundary on its own, so almost every call lands in the
+    /// fast path, whose result must still match a one-shot absorb.
+    #[test]
+    fn many_tiny_updates_match_a_one_shot_update() {
+        let mut rng = SplitMix64(0x5a5a_5a5a_1234_5678);
+
+        for message_len in [0, 1, 2, 135, 136, 137, 1000] {
+            let data = random_message(&mut rng, message_len);
+
+            let mut want = [0u8; 32];
+            crate::Keccak::v256().chain(&data).finalize(&mut want);
+
+            let mut hasher = crate::Keccak::v256();
+            for chunk in data.chunks(1 + (rng.next_u64() as usize % 4)) {
+                hasher.update(chunk);
+            }
+            let mut got = [0u8; 32];
+            hasher.finalize(&mut got);
+
+            assert_eq!(got, want);
+        }
+    }
+}
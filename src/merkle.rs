@@ -0,0 +1,127 @@
+//! A binary Merkle tree over [`keccak256`](crate::keccak256), the hashing
+//! scheme Ethereum and many other chains build their own copy of instead of
+//! sharing one implementation.
+//!
+//! Different chains disagree on two points once a level has an odd number
+//! of nodes, or is empty: whether to duplicate the last node or carry it up
+//! unhashed, and which operand goes on the left when pairing. [`OddPolicy`]
+//! makes that choice explicit rather than hard-coding one chain's rule.
+
+use crate::keccak256;
+
+/// How to handle a tree level with an odd number of nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OddPolicy {
+    /// Pair the last node with itself, so every level has an even number of
+    /// pairs to hash. This is the convention used by Bitcoin's Merkle trees
+    /// and many others.
+    DuplicateLast,
+    /// Carry the last node up to the next level unchanged, without hashing
+    /// it again.
+    CarryUp,
+}
+
+/// Computes the Merkle root of `leaves`, already-hashed 32-byte values,
+/// pairing them upward with `keccak256(left || right)` until a single root
+/// remains, per `odd_policy`.
+///
+/// Returns `[0u8; 32]` for an empty tree, and `leaves[0]` unchanged for a
+/// single-leaf tree (there is nothing to pair it with).
+pub fn merkle_root(leaves: &[[u8; 32]], odd_policy: OddPolicy) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        let mut next = std::vec::Vec::with_capacity(level.len().div_ceil(2));
+        let mut pairs = level.chunks_exact(2);
+        for pair in &mut pairs {
+            next.push(hash_pair(&pair[0], &pair[1]));
+        }
+        if let [last] = pairs.remainder() {
+            next.push(match odd_policy {
+                OddPolicy::DuplicateLast => hash_pair(last, last),
+                OddPolicy::CarryUp => *last,
+            });
+        }
+        level = next;
+    }
+    level[0]
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(left);
+    concatenated[32..].copy_from_slice(right);
+    keccak256(concatenated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(byte: u8) -> [u8; 32] {
+        let mut leaf = [0u8; 32];
+        leaf[0] = byte;
+        keccak256(&leaf)
+    }
+
+    #[test]
+    fn empty_tree_has_the_zero_root() {
+        assert_eq!(merkle_root(&[], OddPolicy::DuplicateLast), [0u8; 32]);
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let a = leaf(1);
+        assert_eq!(merkle_root(&[a], OddPolicy::DuplicateLast), a);
+    }
+
+    #[test]
+    fn two_leaves_hash_to_their_pair() {
+        let a = leaf(1);
+        let b = leaf(2);
+        assert_eq!(merkle_root(&[a, b], OddPolicy::DuplicateLast), hash_pair(&a, &b));
+    }
+
+    #[test]
+    fn three_leaves_duplicate_the_last_under_duplicate_last_policy() {
+        let a = leaf(1);
+        let b = leaf(2);
+        let c = leaf(3);
+
+        let ab = hash_pair(&a, &b);
+        let cc = hash_pair(&c, &c);
+        let want = hash_pair(&ab, &cc);
+
+        assert_eq!(merkle_root(&[a, b, c], OddPolicy::DuplicateLast), want);
+    }
+
+    #[test]
+    fn three_leaves_carry_the_last_up_under_carry_up_policy() {
+        let a = leaf(1);
+        let b = leaf(2);
+        let c = leaf(3);
+
+        let ab = hash_pair(&a, &b);
+        let want = hash_pair(&ab, &c);
+
+        assert_eq!(merkle_root(&[a, b, c], OddPolicy::CarryUp), want);
+    }
+
+    #[test]
+    fn four_leaves_pair_evenly_across_two_levels() {
+        let a = leaf(1);
+        let b = leaf(2);
+        let c = leaf(3);
+        let d = leaf(4);
+
+        let ab = hash_pair(&a, &b);
+        let cd = hash_pair(&c, &d);
+        let want = hash_pair(&ab, &cd);
+
+        assert_eq!(merkle_root(&[a, b, c, d], OddPolicy::DuplicateLast), want);
+        assert_eq!(merkle_root(&[a, b, c, d], OddPolicy::CarryUp), want);
+    }
+}
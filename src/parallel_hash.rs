@@ -0,0 +1,377 @@
+//! `ParallelHashXOF`: the SP800-185 extendable-output tree hash.
+//!
+//! The message is split into fixed-size `B`-byte blocks, each block is
+//! independently hashed with a plain (unkeyed, uncustomized) cSHAKE leaf
+//! hash, and the concatenation of leaf digests (plus a trailing block count)
+//! is absorbed into an outer cSHAKE keyed with the function name
+//! `"ParallelHash"`. Leaf hashing is independent per block, which is what
+//! lets [`finalize_xof_threaded`](ParallelHashXof128::finalize_xof_threaded)
+//! (behind the `parallel-hash-threads` feature) hash blocks across several
+//! OS threads instead of sequentially, since the *output* is defined purely
+//! by the sequence of leaf digests regardless of how they were computed.
+//!
+//! This has not been checked against the SP800-185 `ParallelHash`
+//! known-answer test vectors, only for internal self-consistency (see the
+//! tests below): treat it as a best-effort structural implementation of the
+//! construction rather than a validated one.
+
+use crate::cshake::{CShake128, CShake128Reader, CShake256, CShake256Reader};
+use crate::sp800::right_encode;
+use crate::Hasher;
+
+macro_rules! parallel_hash_xof {
+    ($name:ident, $reader:ident, $cshake:ident, $cshake_reader:ident, $leaf_len:expr, $doc:expr, $reader_doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            outer: $cshake,
+            block_size: usize,
+            pending: std::vec::Vec<u8>,
+            // Completed blocks, buffered rather than leaf-hashed
+            // immediately, so that `finalize_xof_threaded` has a full list
+            // of independent leaf-hash jobs to spread across threads.
+            blocks: std::vec::Vec<std::vec::Vec<u8>>,
+        }
+
+        impl $name {
+            /// Creates a new hasher that splits its input into `block_size`
+            /// byte blocks, with customization string `s`. Pass `&[]` for
+            /// `s` if no customization is needed.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `block_size` is zero.
+            pub fn new(block_size: usize, s: &[u8]) -> Self {
+                assert!(block_size != 0, "block_size must be non-zero");
+                $name {
+                    outer: $cshake::new(b"ParallelHash", s),
+                    block_size,
+                    pending: std::vec::Vec::with_capacity(block_size),
+                    blocks: std::vec::Vec::new(),
+                }
+            }
+
+            fn leaf_hash(block: &[u8]) -> [u8; $leaf_len] {
+                let mut leaf = $cshake::new(&[], &[]);
+                leaf.update(block);
+                let mut leaf_digest = [0u8; $leaf_len];
+                leaf.finalize(&mut leaf_digest);
+                leaf_digest
+            }
+
+            /// Absorbs additional input, queueing any block that becomes
+            /// complete along the way for leaf hashing at finalization.
+            /// Can be called multiple times.
+            pub fn update(&mut self, input: &[u8]) {
+                self.pending.extend_from_slice(input);
+                while self.pending.len() >= self.block_size {
+                    let block =
+                        self.pending.drain(..self.block_size).collect::<std::vec::Vec<u8>>();
+                    self.blocks.push(block);
+                }
+            }
+
+            /// Leaf-hashes every queued block in turn, appends the
+            /// `right_encode`d block count and a trailing `right_encode(0)`
+            /// (per `ParallelHashXOF`'s definition), and returns a reader
+            /// that squeezes output in a sequence of calls instead of one
+            /// fixed-size buffer.
+            #[doc(alias = "into_xof")]
+            pub fn finalize_xof(mut self) -> $reader {
+                if !self.pending.is_empty() {
+                    self.blocks.push(core::mem::take(&mut self.pending));
+                }
+
+                for block in &self.blocks {
+                    self.outer.update(&Self::leaf_hash(block));
+                }
+
+                let mut encoded_n = [0u8; 9];
+                let encoded_n = right_encode(self.blocks.len() as u64, &mut encoded_n);
+                self.outer.update(encoded_n);
+
+                let mut encoded_zero = [0u8; 9];
+                let encoded_zero = right_encode(0, &mut encoded_zero);
+                self.outer.update(encoded_zero);
+
+                $reader(self.outer.finalize_xof())
+            }
+        }
+
+        #[cfg(feature = "parallel-hash-threads")]
+        impl $name {
+            /// Same construction as [`finalize_xof`](Self::finalize_xof),
+            /// but leaf-hashes the queued blocks across up to
+            /// `thread_count` OS threads before combining them: since each
+            /// block's leaf hash is independent of every other, spreading
+            /// them across threads changes nothing about the result, only
+            /// how it's computed. Produces bit-identical output to
+            /// `finalize_xof` for any `thread_count`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `thread_count` is zero.
+            pub fn finalize_xof_threaded(mut self, thread_count: usize) -> $reader {
+                assert!(thread_count != 0, "thread_count must be non-zero");
+
+                if !self.pending.is_empty() {
+                    self.blocks.push(core::mem::take(&mut self.pending));
+                }
+
+                let mut digests = std::vec![[0u8; $leaf_len]; self.blocks.len()];
+                let chunk_size = if self.blocks.is_empty() {
+                    1
+                } else {
+                    self.blocks.len().div_ceil(thread_count)
+                };
+                std::thread::scope(|scope| {
+                    for (block_chunk, digest_chunk) in self
+                        .blocks
+                        .chunks(chunk_size)
+                        .zip(digests.chunks_mut(chunk_size))
+                    {
+                        scope.spawn(move || {
+                            for (block, digest) in block_chunk.iter().zip(digest_chunk.iter_mut())
+                            {
+                                *digest = Self::leaf_hash(block);
+                            }
+                        });
+                    }
+                });
+
+                for digest in &digests {
+                    self.outer.update(digest);
+                }
+
+                let mut encoded_n = [0u8; 9];
+                let encoded_n = right_encode(self.blocks.len() as u64, &mut encoded_n);
+                self.outer.update(encoded_n);
+
+                let mut encoded_zero = [0u8; 9];
+                let encoded_zero = right_encode(0, &mut encoded_zero);
+                self.outer.update(encoded_zero);
+
+                $reader(self.outer.finalize_xof())
+            }
+        }
+
+        // Omits `pending`/`blocks`: those hold raw absorbed message bytes,
+        // not just metadata, so they're left out entirely rather than
+        // truncated or redacted.
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("outer", &self.outer)
+                    .field("block_size", &self.block_size)
+                    .finish()
+            }
+        }
+
+        #[doc = $reader_doc]
+        #[derive(Clone)]
+        pub struct $reader($cshake_reader);
+
+        impl $reader {
+            /// Squeezes `buf.len()` more bytes, continuing from wherever
+            /// the previous `squeeze` call (if any) left off.
+            pub fn squeeze(&mut self, buf: &mut [u8]) {
+                self.0.squeeze(buf);
+            }
+        }
+
+        crate::impl_xof!($name, $reader);
+    };
+}
+
+parallel_hash_xof!(
+    ParallelHashXof128,
+    ParallelHashXof128Reader,
+    CShake128,
+    CShake128Reader,
+    32,
+    "`ParallelHashXOF128`: the 128-bit-security extendable-output parallel \
+     tree hash.",
+    "An extendable-output reader returned by \
+     [`ParallelHashXof128::finalize_xof`]."
+);
+parallel_hash_xof!(
+    ParallelHashXof256,
+    ParallelHashXof256Reader,
+    CShake256,
+    CShake256Reader,
+    64,
+    "`ParallelHashXOF256`: the 256-bit-security extendable-output parallel \
+     tree hash.",
+    "An extendable-output reader returned by \
+     [`ParallelHashXof256::finalize_xof`]."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn squeeze_all(mut hasher: ParallelHashXof256, input: &[u8], out: &mut [u8]) {
+        hasher.update(input);
+        hasher.finalize_xof().squeeze(out);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"hello world", &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"hello world", &mut b);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn an_empty_message_still_finalizes_deterministically() {
+        // Zero absorbed bytes means zero blocks, not zero calls into the
+        // outer cSHAKE (the block count and trailing right_encode(0) are
+        // still absorbed), so this must not be special-cased away.
+        let mut a = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"", &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"", &mut b);
+
+        assert_eq!(a, b);
+        assert_ne!(a, [0u8; 32]);
+    }
+
+    #[test]
+    fn a_non_empty_customization_diverges() {
+        let mut a = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"hello world", &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, b"custom"), b"hello world", &mut b);
+
+        assert_ne!(a, b);
+    }
+
+    // ParallelHash's function-name string `N` ("ParallelHash") is fixed
+    // and non-empty, so the outer cSHAKE must always keep `0x04` framing,
+    // never degrading to plain SHAKE, even with an empty customization
+    // string. Reproduce the exact bytes the outer cSHAKE absorbs
+    // (single-block leaf digest, block count, trailing zero) and confirm
+    // feeding those same bytes to a plain, unnamed/uncustomized cSHAKE
+    // (which *does* degrade to SHAKE) gives a different digest.
+    #[test]
+    fn an_empty_customization_string_still_uses_cshake_framing_not_plain_shake() {
+        let mut got = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(8, &[]), b"short", &mut got);
+
+        let leaf_digest = {
+            let mut leaf = CShake256::new(&[], &[]);
+            leaf.update(b"short");
+            let mut digest = [0u8; 64];
+            leaf.finalize(&mut digest);
+            digest
+        };
+        let mut encoded_n = [0u8; 9];
+        let encoded_n = crate::sp800::right_encode(1, &mut encoded_n);
+        let mut encoded_zero = [0u8; 9];
+        let encoded_zero = crate::sp800::right_encode(0, &mut encoded_zero);
+
+        let mut plain = CShake256::new(&[], &[]);
+        plain.update(&leaf_digest);
+        plain.update(encoded_n);
+        plain.update(encoded_zero);
+        let mut plain_out = [0u8; 32];
+        plain.finalize(&mut plain_out);
+
+        assert_ne!(got, plain_out);
+    }
+
+    #[test]
+    fn different_block_sizes_diverge() {
+        let mut a = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(4, &[]), b"hello world!", &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(6, &[]), b"hello world!", &mut b);
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn exact_multiple_and_non_multiple_lengths_both_work() {
+        // 12 bytes over a block size of 4 divides evenly (3 full blocks);
+        // 13 bytes leaves a trailing partial block. Both must produce
+        // stable, distinct output.
+        let mut exact = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(4, &[]), b"hello world!", &mut exact);
+
+        let mut with_remainder = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(4, &[]), b"hello world!!", &mut with_remainder);
+
+        assert_ne!(exact, with_remainder);
+    }
+
+    #[test]
+    fn chunked_updates_match_a_single_shot_update() {
+        let mut chunked = ParallelHashXof128::new(4, &[]);
+        chunked.update(b"he");
+        chunked.update(b"llo wor");
+        chunked.update(b"ld!");
+        let mut got = [0u8; 32];
+        chunked.finalize_xof().squeeze(&mut got);
+
+        let mut single_shot = ParallelHashXof128::new(4, &[]);
+        single_shot.update(b"hello world!");
+        let mut want = [0u8; 32];
+        single_shot.finalize_xof().squeeze(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[cfg(feature = "parallel-hash-threads")]
+    #[test]
+    fn threaded_finalize_matches_sequential_finalize_for_any_thread_count() {
+        let mut want = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(4, b"custom"), b"hello, threaded world!", &mut want);
+
+        for thread_count in [1, 2, 3, 5, 8] {
+            let mut hasher = ParallelHashXof256::new(4, b"custom");
+            hasher.update(b"hello, threaded world!");
+            let mut got = [0u8; 32];
+            hasher.finalize_xof_threaded(thread_count).squeeze(&mut got);
+
+            assert_eq!(got, want, "mismatch at thread_count = {}", thread_count);
+        }
+    }
+
+    #[cfg(feature = "parallel-hash-threads")]
+    #[test]
+    fn threaded_finalize_matches_sequential_finalize_with_more_threads_than_blocks() {
+        let mut want = [0u8; 32];
+        squeeze_all(ParallelHashXof256::new(64, &[]), b"short", &mut want);
+
+        let mut hasher = ParallelHashXof256::new(64, &[]);
+        hasher.update(b"short");
+        let mut got = [0u8; 32];
+        hasher.finalize_xof_threaded(16).squeeze(&mut got);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn xof_reader_matches_a_single_large_squeeze() {
+        let mut single_shot = ParallelHashXof128::new(4, b"custom");
+        single_shot.update(b"hello world!");
+        let mut want = [0u8; 300];
+        single_shot.finalize_xof().squeeze(&mut want);
+
+        let mut streamed = ParallelHashXof128::new(4, b"custom");
+        streamed.update(b"hello world!");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..17]);
+        reader.squeeze(&mut got[17..]);
+
+        assert_eq!(got, want);
+    }
+}
@@ -0,0 +1,212 @@
+//! [`PrefixMac`]: a lightweight, length-domain-separated prefix-keyed
+//! Keccak-256 MAC, `Keccak256(left_encode(key.len()) || key || message)`.
+//!
+//! This is deliberately much simpler than [`Kmac256`](crate::Kmac256): no
+//! cSHAKE framing, no customization string, no `bytepad`-to-rate-boundary
+//! step, just the key's length prepended to the key itself, then the
+//! message, absorbed by a plain [`Keccak`] sponge. The length prefix exists
+//! so that `key = "ab"`, `message = "cd"` and `key = "a"`, `message = "bcd"`
+//! (which would otherwise both absorb the identical bytes `"abcd"`) produce
+//! different tags. This is a canonical-encoding concern, not a
+//! length-extension one: Keccak's sponge construction (unlike
+//! Merkle-Damgard hashes such as SHA-256) already isn't vulnerable to the
+//! classic length-extension attack, since squeezing re-permutes the full
+//! internal state rather than continuing a raw chained input.
+//!
+//! # Not KMAC, not HMAC
+//!
+//! - Unlike [`Kmac256`](crate::Kmac256), this has no cSHAKE customization
+//!   string and no SP800-185 domain separation beyond the length prefix
+//!   above; it is not interoperable with KMAC and should not be presented
+//!   as an implementation of it.
+//! - Unlike HMAC, this does not run two independently-keyed passes
+//!   (inner/outer) over the hash; it's a single-pass prefix construction
+//!   that's safe *because* Keccak is a sponge, not because it reproduces
+//!   HMAC's construction. Porting this exact pattern to a Merkle-Damgard
+//!   hash (e.g. plain SHA-256) would reintroduce length-extension and must
+//!   not be done.
+//!
+//! This has not been checked against any external test vectors — there is
+//! no standard body defining this exact construction to check against —
+//! only for internal self-consistency (see the tests below).
+
+use crate::sp800::left_encode;
+use crate::{Hasher, Keccak};
+
+/// Compares two equal-length byte slices without short-circuiting on the
+/// first difference. Duplicated from the shape of [`crate::ct_eq`] rather
+/// than depending on the `ct-eq` feature, matching the precedent set by
+/// `hmac.rs`'s own internal copy.
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// A prefix-keyed Keccak-256 MAC. See the module docs for how (and why)
+/// this differs from [`Kmac256`](crate::Kmac256) and from HMAC.
+#[derive(Clone)]
+pub struct PrefixMac {
+    keccak: Keccak,
+}
+
+impl PrefixMac {
+    /// The fixed tag length, in bytes.
+    pub const TAG_LEN: usize = 32;
+
+    /// Creates a new MAC keyed with `key`. Any length, including empty and
+    /// longer than the sponge rate, is accepted: the length is absorbed
+    /// first (as `left_encode(key.len())`), so the sponge sees an
+    /// unambiguous, self-delimiting prefix no matter how long `key` is.
+    pub fn new(key: &[u8]) -> Self {
+        let mut keccak = Keccak::v256();
+        let mut encoded_len = [0u8; 9];
+        let encoded_len = left_encode(key.len() as u64, &mut encoded_len);
+        keccak.update(encoded_len);
+        keccak.update(key);
+        PrefixMac { keccak }
+    }
+
+    /// Absorb additional message input. Can be called multiple times.
+    pub fn update(&mut self, input: &[u8]) {
+        self.keccak.update(input);
+    }
+
+    /// Pads and squeezes the fixed-size tag.
+    pub fn finalize(self) -> [u8; Self::TAG_LEN] {
+        let mut tag = [0u8; Self::TAG_LEN];
+        Hasher::finalize(self.keccak, &mut tag);
+        tag
+    }
+
+    /// Computes the tag and compares it to `expected` in constant time,
+    /// returning whether they match. Returns `false` (rather than
+    /// panicking) if `expected`'s length doesn't match [`Self::TAG_LEN`].
+    pub fn verify(self, expected: &[u8]) -> bool {
+        let tag = self.finalize();
+        ct_eq(&tag, expected)
+    }
+}
+
+impl crate::Mac for PrefixMac {
+    const TAG_LEN: usize = Self::TAG_LEN;
+
+    fn new(key: &[u8]) -> Self {
+        Self::new(key)
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Self::update(self, input)
+    }
+
+    fn finalize_into(self, output: &mut [u8]) {
+        output.copy_from_slice(&self.finalize());
+    }
+
+    fn verify(&self, tag: &[u8]) -> bool {
+        self.clone().verify(tag)
+    }
+}
+
+// Forwards to the inner `Keccak`'s own `Debug`, which only prints `rate`/
+// `delim`, never the buffer the key and message were absorbed into.
+impl core::fmt::Debug for PrefixMac {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PrefixMac").field("keccak", &self.keccak).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_key_and_message_is_deterministic() {
+        let mut a = PrefixMac::new(b"key");
+        a.update(b"message");
+        let mut b = PrefixMac::new(b"key");
+        b.update(b"message");
+        assert_eq!(a.finalize(), b.finalize());
+    }
+
+    #[test]
+    fn an_empty_key_is_accepted_and_diverges_from_a_non_empty_one() {
+        let mut empty_key = PrefixMac::new(b"");
+        empty_key.update(b"message");
+
+        let mut non_empty_key = PrefixMac::new(b"k");
+        non_empty_key.update(b"message");
+
+        assert_ne!(empty_key.finalize(), non_empty_key.finalize());
+    }
+
+    #[test]
+    fn a_key_longer_than_the_rate_is_accepted() {
+        // Keccak-256's rate is 136 bytes; use a key well past it to make
+        // sure absorbing the key isn't silently truncated to one block.
+        let long_key = [0x5au8; 200];
+        let mut mac = PrefixMac::new(&long_key);
+        mac.update(b"message");
+        let tag = mac.finalize();
+
+        let mut same = PrefixMac::new(&long_key);
+        same.update(b"message");
+        assert_eq!(tag, same.finalize());
+
+        let mut truncated_key = long_key;
+        truncated_key[199] ^= 0xff;
+        let mut different = PrefixMac::new(&truncated_key);
+        different.update(b"message");
+        assert_ne!(tag, different.finalize());
+    }
+
+    #[test]
+    fn the_length_prefix_disambiguates_key_message_boundaries() {
+        let mut split_early = PrefixMac::new(b"ab");
+        split_early.update(b"cd");
+
+        let mut split_late = PrefixMac::new(b"a");
+        split_late.update(b"bcd");
+
+        assert_ne!(split_early.finalize(), split_late.finalize());
+    }
+
+    #[test]
+    fn verify_accepts_a_correct_tag() {
+        let mut mac = PrefixMac::new(b"key");
+        mac.update(b"message");
+        let tag = mac.finalize();
+
+        let mut verifier = PrefixMac::new(b"key");
+        verifier.update(b"message");
+        assert!(verifier.verify(&tag));
+    }
+
+    #[test]
+    fn verify_rejects_a_flipped_bit() {
+        let mut mac = PrefixMac::new(b"key");
+        mac.update(b"message");
+        let mut tag = mac.finalize();
+        tag[0] ^= 0x01;
+
+        let mut verifier = PrefixMac::new(b"key");
+        verifier.update(b"message");
+        assert!(!verifier.verify(&tag));
+    }
+
+    #[test]
+    fn verify_rejects_a_wrong_length_tag_instead_of_panicking() {
+        let mut mac = PrefixMac::new(b"key");
+        mac.update(b"message");
+        let tag = mac.finalize();
+
+        let mut verifier = PrefixMac::new(b"key");
+        verifier.update(b"message");
+        assert!(!verifier.verify(&tag[..tag.len() - 1]));
+    }
+}
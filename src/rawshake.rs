@@ -0,0 +1,141 @@
+//! The `RawSHAKE` extendable-output functions: the same sponge as `SHAKE`
+//! but with the `0x07` domain separator that cSHAKE, KMAC, TupleHash and the
+//! other SP800-185 constructions build on top of, exposed directly so
+//! callers can define their own domain-separated functions without
+//! reaching into this crate's internals.
+
+use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState, XofReader};
+
+macro_rules! rawshake {
+    ($name:ident, $reader:ident, $doc:expr, $bits:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            state: KeccakState<KeccakF>,
+        }
+
+        impl $name {
+            const DELIM: u8 = 0x07;
+
+            /// Creates a new hasher.
+            pub fn new() -> Self {
+                $name {
+                    state: KeccakState::new(bits_to_rate($bits), Self::DELIM),
+                }
+            }
+
+            /// Pads the absorbed input and returns a reader that squeezes
+            /// output in a sequence of calls instead of one fixed-size
+            /// buffer.
+            #[doc(alias = "into_xof")]
+            pub fn finalize_xof(self) -> $reader {
+                $reader(XofReader::new(self.state))
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Hasher for $name {
+            fn update(&mut self, input: &[u8]) {
+                self.state.update(input);
+            }
+
+            fn finalize(self, output: &mut [u8]) {
+                self.state.finalize(output);
+            }
+
+            fn reset(&mut self) {
+                self.state.reset();
+            }
+
+            fn finalize_reset(&mut self, output: &mut [u8]) {
+                self.state.finalize_reset(output);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        crate::impl_io_write!($name);
+        crate::impl_fmt_write!($name);
+        crate::impl_debug_via_state!($name);
+        crate::impl_xof!($name, $reader);
+
+        #[doc = concat!("An extendable-output reader returned by [`", stringify!($name), "::finalize_xof`].")]
+        #[derive(Clone)]
+        pub struct $reader(XofReader<KeccakF>);
+
+        impl $reader {
+            /// Squeezes `buf.len()` more bytes, continuing from wherever the
+            /// previous `squeeze` call (if any) left off.
+            pub fn squeeze(&mut self, buf: &mut [u8]) {
+                self.0.squeeze(buf);
+            }
+        }
+    };
+}
+
+rawshake!(
+    RawShake128,
+    RawShake128Reader,
+    "`RawSHAKE128`: `SHAKE128`'s sponge with the raw `0x07` domain separator.",
+    128
+);
+rawshake!(
+    RawShake256,
+    RawShake256Reader,
+    "`RawSHAKE256`: `SHAKE256`'s sponge with the raw `0x07` domain separator.",
+    256
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_shake_of_empty_input_is_deterministic() {
+        let a = RawShake128::new();
+        let b = RawShake128::default();
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_eq!(out_a, out_b);
+    }
+
+    #[test]
+    fn raw_shake_differs_from_standard_shake() {
+        let mut raw = RawShake128::new();
+        raw.update(b"hello");
+        let mut raw_out = [0u8; 32];
+        raw.finalize(&mut raw_out);
+
+        #[cfg(feature = "shake")]
+        {
+            let mut shake = crate::Shake::v128();
+            shake.update(b"hello");
+            let mut shake_out = [0u8; 32];
+            shake.finalize(&mut shake_out);
+            assert_ne!(raw_out, shake_out);
+        }
+    }
+
+    #[test]
+    fn multi_block_squeeze_matches_single_shot() {
+        let mut single_shot = RawShake256::new();
+        single_shot.update(b"hello");
+        let mut want = [0u8; 300];
+        single_shot.finalize(&mut want);
+
+        let mut streamed = RawShake256::new();
+        streamed.update(b"hello");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..136]);
+        reader.squeeze(&mut got[136..]);
+
+        assert_eq!(got, want);
+    }
+}
@@ -0,0 +1,215 @@
+//! [`ReducedRoundKeccak`]: a Keccak-`f[1600]` sponge whose round count is
+//! chosen at construction time instead of being fixed by the type, for
+//! cryptanalysis and fuzzing of reduced-round Keccak.
+//!
+//! **Research/testing use only.** Every standard hasher in this crate
+//! ([`Keccak`](crate::Keccak), [`Sha3`](crate::Sha3), [`Shake`](crate::Shake),
+//! ...) always runs the full 24-round [`KeccakF`](crate::keccakf::KeccakF)
+//! permutation; nothing here changes that. `ReducedRoundKeccak` exists
+//! purely so reduced-round variants can be run and compared without
+//! recompiling against a different `ROUNDS` const generic each time, and
+//! fewer than 24 rounds is not believed to provide Keccak's usual security
+//! margin.
+
+use crate::{keccakf::keccak_p, Buffer, Hasher};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Absorbing,
+    Squeezing,
+}
+
+/// A Keccak-`f[1600]` sponge hasher with a runtime-chosen round count.
+///
+/// At `rounds == 24` this behaves exactly like standard Keccak (see the
+/// test below); at any smaller count, the digest is **not** a standard
+/// Keccak/SHA-3/SHAKE output and must not be treated as one.
+#[derive(Clone)]
+pub struct ReducedRoundKeccak {
+    buffer: Buffer<u64>,
+    offset: usize,
+    rate: usize,
+    delim: u8,
+    rounds: usize,
+    phase: Phase,
+}
+
+impl ReducedRoundKeccak {
+    /// Creates a hasher with the given `rate` (in bytes) and
+    /// domain-separation suffix `delim` (see
+    /// [`Keccak::custom`](crate::Keccak::custom) for what `delim` means),
+    /// running `rounds` rounds of `f[1600]` per permutation call instead of
+    /// the standard 24.
+    ///
+    /// `rounds` takes the *last* `rounds` entries of the 24-round schedule,
+    /// matching [`KeccakFRounds`](crate::keccakf::KeccakFRounds) (the
+    /// const-generic permutation `KeccakF`/`KeccakF12` are both built on).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is 0 or `rounds` exceeds 24.
+    pub fn with_rounds(rate: usize, delim: u8, rounds: usize) -> Self {
+        assert!(rate != 0, "rate cannot be equal 0");
+        assert!(rounds <= 24, "rounds cannot exceed the standard 24-round schedule");
+        ReducedRoundKeccak {
+            buffer: Buffer::default(),
+            offset: 0,
+            rate,
+            delim,
+            rounds,
+            phase: Phase::Absorbing,
+        }
+    }
+
+    fn permute(&mut self) {
+        keccak_p(self.buffer.words(), self.rounds);
+    }
+
+    fn pad(&mut self) {
+        self.buffer.pad(self.offset, self.delim, self.rate);
+        self.phase = Phase::Squeezing;
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        let rate = self.rate;
+        let mut output = output;
+
+        while output.len() >= rate {
+            self.buffer.setout(&mut output[..rate], 0, rate);
+            self.permute();
+            output = &mut output[rate..];
+        }
+
+        let len = output.len();
+        self.buffer.setout(output, 0, len);
+    }
+}
+
+impl Hasher for ReducedRoundKeccak {
+    fn update(&mut self, input: &[u8]) {
+        debug_assert!(
+            self.phase == Phase::Absorbing,
+            "cannot absorb more input after squeezing has begun",
+        );
+        let mut input = input;
+        let rate = self.rate;
+        if self.offset != 0 {
+            let head_len = rate - self.offset;
+            let head_len = core::cmp::min(head_len, input.len());
+            self.buffer.xorin(&input[..head_len], self.offset, head_len);
+            self.offset += head_len;
+            input = &input[head_len..];
+            if self.offset != rate {
+                return;
+            }
+            self.permute();
+            self.offset = 0;
+        }
+
+        while input.len() >= rate {
+            self.buffer.xorin(&input[..rate], 0, rate);
+            self.permute();
+            input = &input[rate..];
+        }
+
+        if !input.is_empty() {
+            self.buffer.xorin(input, 0, input.len());
+            self.offset = input.len();
+        }
+    }
+
+    fn finalize(mut self, output: &mut [u8]) {
+        self.pad();
+        self.permute();
+        self.squeeze(output);
+    }
+
+    fn reset(&mut self) {
+        self.buffer = Buffer::default();
+        self.offset = 0;
+        self.phase = Phase::Absorbing;
+    }
+
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.pad();
+        self.permute();
+        self.squeeze(output);
+        self.reset();
+    }
+}
+
+#[cfg(feature = "std")]
+crate::impl_io_write!(ReducedRoundKeccak);
+crate::impl_fmt_write!(ReducedRoundKeccak);
+
+/// Prints `rate`, `delim` and `rounds`, but never `buffer`/`offset`, for the
+/// same reason [`KeccakState`](crate::KeccakState)'s own `Debug` impl omits
+/// them: the buffer can hold input the caller didn't intend to log.
+impl core::fmt::Debug for ReducedRoundKeccak {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReducedRoundKeccak")
+            .field("rate", &self.rate)
+            .field("delim", &self.delim)
+            .field("rounds", &self.rounds)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "keccak")]
+    #[test]
+    fn with_rounds_24_matches_standard_keccak() {
+        use crate::Keccak;
+
+        let mut reduced = ReducedRoundKeccak::with_rounds(136, 0x01, 24);
+        reduced.update(b"hello");
+        let mut got = [0u8; 32];
+        reduced.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        Keccak::keccak256(b"hello", &mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn fewer_rounds_diverges_from_the_full_schedule() {
+        let mut full = ReducedRoundKeccak::with_rounds(136, 0x01, 24);
+        full.update(b"hello");
+        let mut full_out = [0u8; 32];
+        full.finalize(&mut full_out);
+
+        let mut reduced = ReducedRoundKeccak::with_rounds(136, 0x01, 12);
+        reduced.update(b"hello");
+        let mut reduced_out = [0u8; 32];
+        reduced.finalize(&mut reduced_out);
+
+        assert_ne!(full_out, reduced_out);
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher() {
+        let mut hasher = ReducedRoundKeccak::with_rounds(136, 0x01, 12);
+        hasher.update(b"garbage to be discarded");
+        hasher.reset();
+        hasher.update(b"hello");
+        let mut got = [0u8; 32];
+        hasher.finalize(&mut got);
+
+        let mut reduced = ReducedRoundKeccak::with_rounds(136, 0x01, 12);
+        reduced.update(b"hello");
+        let mut want = [0u8; 32];
+        reduced.finalize(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    #[should_panic(expected = "rounds cannot exceed")]
+    fn rejects_more_than_24_rounds() {
+        ReducedRoundKeccak::with_rounds(136, 0x01, 25);
+    }
+}
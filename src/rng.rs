@@ -0,0 +1,145 @@
+//! [`ShakeRng`]: a deterministic byte stream generator built on SHAKE256,
+//! for reproducible randomness in tests and protocols, plus optional
+//! interop with the `rand_core` ecosystem's `RngCore`/`SeedableRng` traits.
+//!
+//! The `rand_core` interop below only exists as source: this snapshot has no
+//! `Cargo.toml` to declare `rand_core = "0.6"` as a dependency of, so
+//! `RngCore`/`SeedableRng` have never actually been resolved, let alone
+//! type-checked, against the real crate. Read the impls here as a wiring
+//! sketch of what they'd need to look like, not as code anyone has run.
+//!
+//! `ShakeRng` is a deterministic XOF-backed stream, not a CSPRNG: it has no
+//! reseed policy of its own beyond the caller explicitly calling
+//! [`reseed`](ShakeRng::reseed), and two `ShakeRng`s built `from_seed` with
+//! the same seed always produce identical output on every platform.
+
+use crate::{Hasher, Shake, ShakeReader};
+
+/// A deterministic byte stream generator: seed it once via
+/// [`from_seed`](Self::from_seed), then draw output via
+/// [`fill_bytes`](Self::fill_bytes) (or, behind `rand_core` interop, the
+/// standard [`RngCore`] trait). The same seed always produces the same
+/// stream; call [`reseed`](Self::reseed) to fold in fresh entropy partway
+/// through.
+pub struct ShakeRng {
+    reader: ShakeReader,
+}
+
+impl ShakeRng {
+    /// Seeds a new stream from `seed`. Two `ShakeRng`s built from the same
+    /// `seed` produce byte-for-byte identical streams.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut shake = Shake::v256();
+        shake.update(seed);
+        ShakeRng {
+            reader: shake.finalize_xof(),
+        }
+    }
+
+    /// Fills `out` with the next `out.len()` bytes of the stream.
+    pub fn fill_bytes(&mut self, out: &mut [u8]) {
+        self.reader.squeeze(out);
+    }
+
+    /// Folds `data` into the stream, so all output from this point on
+    /// depends on both `data` and everything drawn so far: draws 32 fresh
+    /// bytes from the current stream, absorbs them together with `data`
+    /// into a new SHAKE256 instance, and continues from that instance's
+    /// output instead.
+    pub fn reseed(&mut self, data: &[u8]) {
+        let mut carry = [0u8; 32];
+        self.reader.squeeze(&mut carry);
+        let mut shake = Shake::v256();
+        shake.update(&carry);
+        shake.update(data);
+        self.reader = shake.finalize_xof();
+    }
+}
+
+#[cfg(feature = "rand_core")]
+mod rand_core_impl {
+    use super::ShakeRng;
+    use rand_core::{RngCore, SeedableRng};
+
+    impl RngCore for ShakeRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut buf = [0u8; 4];
+            self.fill_bytes(&mut buf);
+            u32::from_le_bytes(buf)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut buf = [0u8; 8];
+            self.fill_bytes(&mut buf);
+            u64::from_le_bytes(buf)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            ShakeRng::fill_bytes(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl SeedableRng for ShakeRng {
+        type Seed = [u8; 32];
+
+        fn from_seed(seed: Self::Seed) -> Self {
+            ShakeRng::from_seed(&seed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_seeds_produce_identical_streams() {
+        let mut a = ShakeRng::from_seed(b"seed");
+        let mut b = ShakeRng::from_seed(b"seed");
+
+        let mut out_a = [0u8; 64];
+        let mut out_b = [0u8; 64];
+        a.fill_bytes(&mut out_a);
+        b.fill_bytes(&mut out_b);
+
+        assert_eq!(out_a, out_b);
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut a = ShakeRng::from_seed(b"seed one");
+        let mut b = ShakeRng::from_seed(b"seed two");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.fill_bytes(&mut out_a);
+        b.fill_bytes(&mut out_b);
+
+        assert_ne!(out_a, out_b);
+    }
+
+    #[test]
+    fn reseeding_diverges_from_the_unreseeded_stream() {
+        let mut a = ShakeRng::from_seed(b"seed");
+        let mut b = ShakeRng::from_seed(b"seed");
+
+        let mut before_a = [0u8; 16];
+        let mut before_b = [0u8; 16];
+        a.fill_bytes(&mut before_a);
+        b.fill_bytes(&mut before_b);
+        assert_eq!(before_a, before_b, "streams agree before reseeding");
+
+        b.reseed(b"fresh entropy");
+
+        let mut after_a = [0u8; 16];
+        let mut after_b = [0u8; 16];
+        a.fill_bytes(&mut after_a);
+        b.fill_bytes(&mut after_b);
+        assert_ne!(after_a, after_b, "reseeding must change the stream");
+    }
+}
@@ -0,0 +1,99 @@
+//! `serde` support for snapshotting an in-progress sponge state so it can be
+//! persisted (e.g. alongside a checkpointed upload) and later resumed to
+//! produce a bit-identical final digest.
+//!
+//! The wire format is the raw sponge bytes plus `offset`/`rate`/`delim`,
+//! independent of the permutation's lane width, so it round-trips through
+//! any `serde` data format. Deserializing a state whose `offset`/`rate`
+//! don't fit the buffer size returns an error rather than panicking or
+//! silently producing garbage output.
+//!
+//! Requires the `std` feature: the raw bytes are staged through a `Vec<u8>`
+//! rather than a lane-width-specific fixed array.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{KeccakState, Permutation};
+
+#[derive(Serialize, Deserialize)]
+struct KeccakStateRepr {
+    buffer: std::vec::Vec<u8>,
+    offset: usize,
+    rate: usize,
+    delim: u8,
+}
+
+impl<P: Permutation> Serialize for KeccakState<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        KeccakStateRepr {
+            buffer: self.raw_bytes(),
+            offset: self.offset,
+            rate: self.rate,
+            delim: self.delim,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, P: Permutation> Deserialize<'de> for KeccakState<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = KeccakStateRepr::deserialize(deserializer)?;
+        KeccakState::from_raw_parts(&repr.buffer, repr.offset, repr.rate, repr.delim)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keccakf::KeccakF;
+    use crate::Hasher;
+
+    #[test]
+    fn round_trip_matches_an_uninterrupted_hash() {
+        let message = b"the quick brown fox jumps over the lazy dog";
+        let (first_half, second_half) = message.split_at(message.len() / 2);
+
+        let mut uninterrupted: KeccakState<KeccakF> = KeccakState::new(136, 0x01);
+        uninterrupted.update(message);
+        let mut want = [0u8; 32];
+        uninterrupted.finalize(&mut want);
+
+        let mut resumed: KeccakState<KeccakF> = KeccakState::new(136, 0x01);
+        resumed.update(first_half);
+        let snapshot = serde_json::to_vec(&resumed).unwrap();
+        let mut resumed: KeccakState<KeccakF> = serde_json::from_slice(&snapshot).unwrap();
+        resumed.update(second_half);
+        let mut got = [0u8; 32];
+        resumed.finalize(&mut got);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn deserializing_an_out_of_range_offset_is_an_error() {
+        let bad = KeccakStateRepr {
+            buffer: std::vec![0u8; 200],
+            offset: 999,
+            rate: 136,
+            delim: 0x01,
+        };
+        let json = serde_json::to_vec(&bad).unwrap();
+        let result: Result<KeccakState<KeccakF>, _> = serde_json::from_slice(&json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn deserializing_a_buffer_of_the_wrong_size_is_an_error() {
+        let bad = KeccakStateRepr {
+            buffer: std::vec![0u8; 25], // f[200]'s size, not f[1600]'s
+            offset: 0,
+            rate: 136,
+            delim: 0x01,
+        };
+        let json = serde_json::to_vec(&bad).unwrap();
+        let result: Result<KeccakState<KeccakF>, _> = serde_json::from_slice(&json);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,579 @@
+//! The `SHA3` hash functions.
+
+use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState};
+
+/// The `SHA3` hash functions defined in [`FIPS-202`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["sha3"] }
+/// ```
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+#[derive(Clone)]
+pub struct Sha3 {
+    state: KeccakState<KeccakF>,
+    output_bytes: usize,
+}
+
+impl Sha3 {
+    const DELIM: u8 = 0x06;
+
+    /// Creates  new [`Sha3`] hasher with a security level of 224 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v224() -> Sha3 {
+        Sha3::new(224)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 256 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v256() -> Sha3 {
+        Sha3::new(256)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 384 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v384() -> Sha3 {
+        Sha3::new(384)
+    }
+
+    /// Creates  new [`Sha3`] hasher with a security level of 512 bits.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    pub fn v512() -> Sha3 {
+        Sha3::new(512)
+    }
+
+    fn new(bits: usize) -> Sha3 {
+        Sha3 {
+            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+            output_bytes: bits / 8,
+        }
+    }
+
+    /// Pads, squeezes and returns the digest as a fixed-size array, checking
+    /// `N` against the security level this hasher was constructed with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` does not match the output length implied by the
+    /// `vNNN()` constructor used to create this hasher.
+    pub fn finalize_array<const N: usize>(self) -> [u8; N] {
+        assert_eq!(
+            N, self.output_bytes,
+            "output array length does not match the configured security level",
+        );
+        let mut output = [0u8; N];
+        self.finalize(&mut output);
+        output
+    }
+
+    /// Pads and squeezes the digest into `output`, returning
+    /// [`InvalidOutputLen`] instead of silently truncating or under-filling
+    /// it if `output.len()` doesn't match the security level this hasher
+    /// was constructed with.
+    ///
+    /// Prefer [`finalize_array`](Self::finalize_array) when the length is
+    /// known at compile time; this is for callers who only learn the
+    /// buffer's length at runtime and want the mismatch caught rather than
+    /// silently producing a truncated digest.
+    pub fn try_finalize(self, output: &mut [u8]) -> Result<(), crate::InvalidOutputLen> {
+        if output.len() != self.output_bytes {
+            return Err(crate::InvalidOutputLen);
+        }
+        self.finalize(output);
+        Ok(())
+    }
+
+    /// Pads, squeezes and hex-encodes the digest in one call, for the
+    /// common case of immediately logging or JSON-serializing a hash
+    /// without pulling in a separate `hex` crate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::Sha3;
+    ///
+    /// assert_eq!(
+    ///     Sha3::v256().finalize_hex(),
+    ///     "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+    /// );
+    /// ```
+    #[cfg(all(feature = "hex", feature = "alloc"))]
+    pub fn finalize_hex(self) -> alloc::string::String {
+        let mut output = alloc::vec![0u8; self.output_bytes];
+        self.finalize(&mut output);
+        crate::hex::to_hex_string(&output)
+    }
+
+    /// Pads, squeezes and wraps the digest in a [`CtDigest`](crate::CtDigest),
+    /// so it can be stored and compared with `==` without reintroducing a
+    /// variable-time comparison. Otherwise identical to
+    /// [`finalize_array`](Self::finalize_array), including the panic on a
+    /// mismatched `N`.
+    #[cfg(feature = "ct-eq")]
+    pub fn finalize_ct_array<const N: usize>(self) -> crate::CtDigest<N> {
+        crate::CtDigest::from(self.finalize_array())
+    }
+
+    /// Computes the SHA3-224 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v224()` followed by `update` and `finalize`.
+    pub fn sha3_224(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v224();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-256 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v256()` followed by `update` and `finalize`.
+    pub fn sha3_256(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v256();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-384 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v384()` followed by `update` and `finalize`.
+    pub fn sha3_384(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v384();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+
+    /// Computes the SHA3-512 digest of `input` in one call, writing the
+    /// result into `output`.
+    ///
+    /// Equivalent to `Sha3::v512()` followed by `update` and `finalize`.
+    pub fn sha3_512(input: &[u8], output: &mut [u8]) {
+        let mut sha3 = Sha3::v512();
+        sha3.update(input);
+        sha3.finalize(output);
+    }
+}
+
+impl Hasher for Sha3 {
+    /// Absorb additional input. Can be called multiple times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// #
+    /// # fn main() {
+    /// # let mut sha3 = Sha3::v256();
+    /// sha3.update(b"hello");
+    /// sha3.update(b" world");
+    /// # }
+    /// ```
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Pad and squeeze the state to the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Sha3};
+    /// #
+    /// # fn main() {
+    /// # let sha3 = Sha3::v256();
+    /// # let mut output = [0u8; 32];
+    /// sha3.finalize(&mut output);
+    /// # }
+    /// #
+    /// ```
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Sha3`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Sha3`]: struct.Sha3.html
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+#[cfg(feature = "std")]
+crate::impl_io_write!(Sha3);
+crate::impl_fmt_write!(Sha3);
+crate::impl_debug_via_state!(Sha3);
+
+impl Sha3 {
+    /// Absorbs a compile-time-sized `data`, behaviorally identical to
+    /// `update(data)` but with `N` known at the call site, which lets the
+    /// optimizer elide the general absorb loop's bounds checks. Useful for
+    /// hashing fixed-size structs.
+    pub fn update_fixed<const N: usize>(&mut self, data: &[u8; N]) {
+        self.state.update_fixed(data);
+    }
+
+    /// Finalizes `other` into a stack buffer sized by its
+    /// [`Hasher::OUTPUT_LEN`] and absorbs the result, for hash-of-hash and
+    /// commitment-chain constructions. Only meaningful for `H` whose
+    /// `OUTPUT_LEN` is a real, non-zero per-type constant (e.g.
+    /// [`HmacSha3_256`](crate::HmacSha3_256)); panics if `H::OUTPUT_LEN` is
+    /// `0` or exceeds 64 bytes.
+    pub fn update_digest<H: Hasher>(&mut self, other: H) {
+        self.state.update_digest(other);
+    }
+
+    /// Absorbs `words` directly into the rate lanes as little-endian
+    /// 64-bit words, skipping the byte-repacking [`update`](Hasher::update)
+    /// does internally. Useful for callers (e.g. zk provers) that already
+    /// have word-aligned data.
+    ///
+    /// Equivalent to calling `update(&word.to_le_bytes())` for each word,
+    /// but without the intermediate byte buffer.
+    pub fn update_words(&mut self, words: &[u64]) {
+        self.state.update_words(words);
+    }
+
+    /// The sponge rate, in bytes, this hasher was constructed with.
+    pub fn rate(&self) -> usize {
+        self.state.rate()
+    }
+
+    /// The sponge capacity, in bits, this hasher was constructed with.
+    pub fn capacity_bits(&self) -> usize {
+        self.state.capacity_bits()
+    }
+
+    /// The total number of bytes passed to [`update`](Hasher::update) since
+    /// construction or the last [`reset`](Hasher::reset).
+    pub fn bytes_absorbed(&self) -> u64 {
+        self.state.bytes_absorbed()
+    }
+}
+
+/// Computes the SHA3-224 digest of `data`, returning it by value.
+///
+/// Unlike [`Sha3::sha3_224`], this accepts anything that derefs to `&[u8]`
+/// (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array instead
+/// of writing into a caller-supplied buffer.
+pub fn sha3_224(data: impl AsRef<[u8]>) -> [u8; 28] {
+    let mut sha3 = Sha3::v224();
+    sha3.update(data.as_ref());
+    sha3.finalize_array()
+}
+
+/// Computes the SHA3-256 digest of `data`, returning it by value.
+///
+/// Unlike [`Sha3::sha3_256`], this accepts anything that derefs to `&[u8]`
+/// (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array instead
+/// of writing into a caller-supplied buffer.
+///
+/// # Example
+///
+/// ```
+/// use tiny_keccak::sha3_256;
+///
+/// assert_eq!(
+///     sha3_256(b""),
+///     [
+///         0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61,
+///         0xd6, 0x62, 0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b,
+///         0x80, 0xf8, 0x43, 0x4a,
+///     ]
+/// );
+/// ```
+pub fn sha3_256(data: impl AsRef<[u8]>) -> [u8; 32] {
+    let mut sha3 = Sha3::v256();
+    sha3.update(data.as_ref());
+    sha3.finalize_array()
+}
+
+/// Computes the SHA3-384 digest of `data`, returning it by value.
+///
+/// Unlike [`Sha3::sha3_384`], this accepts anything that derefs to `&[u8]`
+/// (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array instead
+/// of writing into a caller-supplied buffer.
+pub fn sha3_384(data: impl AsRef<[u8]>) -> [u8; 48] {
+    let mut sha3 = Sha3::v384();
+    sha3.update(data.as_ref());
+    sha3.finalize_array()
+}
+
+/// Computes the SHA3-512 digest of `data`, returning it by value.
+///
+/// Unlike [`Sha3::sha3_512`], this accepts anything that derefs to `&[u8]`
+/// (`&str`, `Vec<u8>`, `&[u8]`, ...) and hands back a stack array instead
+/// of writing into a caller-supplied buffer.
+pub fn sha3_512(data: impl AsRef<[u8]>) -> [u8; 64] {
+    let mut sha3 = Sha3::v512();
+    sha3.update(data.as_ref());
+    sha3.finalize_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known vector: FIPS-202 SHA3-256("").
+    #[test]
+    fn sha3_256_of_empty_input_matches_known_vector() {
+        let mut sha3 = Sha3::v256();
+        let mut output = [0u8; 32];
+        sha3.update(b"");
+        sha3.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61,
+                0xd6, 0x62, 0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b,
+                0x80, 0xf8, 0x43, 0x4a,
+            ]
+        );
+    }
+
+    #[test]
+    fn sha3_256_of_hello_matches_known_vector() {
+        let mut sha3 = Sha3::v256();
+        let mut output = [0u8; 32];
+        sha3.update(b"hello");
+        sha3.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x33, 0x38, 0xbe, 0x69, 0x4f, 0x50, 0xc5, 0xf3, 0x38, 0x81, 0x49, 0x86, 0xcd, 0xf0,
+                0x68, 0x64, 0x53, 0xa8, 0x88, 0xb8, 0x4f, 0x42, 0x4d, 0x79, 0x2a, 0xf4, 0xb9, 0x20,
+                0x23, 0x98, 0xf3, 0x92,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-224("hello").
+    #[test]
+    fn sha3_224_one_shot_matches_known_vector() {
+        let mut output = [0u8; 28];
+        Sha3::sha3_224(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0xb8, 0x7f, 0x88, 0xc7, 0x27, 0x02, 0xff, 0xf1, 0x74, 0x8e, 0x58, 0xb8, 0x7e, 0x91,
+                0x41, 0xa4, 0x2c, 0x0d, 0xbe, 0xdc, 0x29, 0xa7, 0x8c, 0xb0, 0xd4, 0xa5, 0xcd, 0x81,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-224("").
+    #[test]
+    fn sha3_224_of_empty_input_matches_known_vector() {
+        let mut output = [0u8; 28];
+        Sha3::sha3_224(b"", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x6b, 0x4e, 0x03, 0x42, 0x36, 0x67, 0xdb, 0xb7, 0x3b, 0x6e, 0x15, 0x45, 0x4f, 0x0e,
+                0xb1, 0xab, 0xd4, 0x59, 0x7f, 0x9a, 0x1b, 0x07, 0x8e, 0x3f, 0x5b, 0x5a, 0x6b, 0xc7,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-384("hello").
+    #[test]
+    fn sha3_384_one_shot_matches_known_vector() {
+        let mut output = [0u8; 48];
+        Sha3::sha3_384(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x72, 0x0a, 0xea, 0x11, 0x01, 0x9e, 0xf0, 0x64, 0x40, 0xfb, 0xf0, 0x5d, 0x87, 0xaa,
+                0x24, 0x68, 0x0a, 0x21, 0x53, 0xdf, 0x39, 0x07, 0xb2, 0x36, 0x31, 0xe7, 0x17, 0x7c,
+                0xe6, 0x20, 0xfa, 0x13, 0x30, 0xff, 0x07, 0xc0, 0xfd, 0xde, 0xe5, 0x46, 0x99, 0xa4,
+                0xc3, 0xee, 0x0e, 0xe9, 0xd8, 0x87,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-384("").
+    #[test]
+    fn sha3_384_of_empty_input_matches_known_vector() {
+        let mut output = [0u8; 48];
+        Sha3::sha3_384(b"", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x0c, 0x63, 0xa7, 0x5b, 0x84, 0x5e, 0x4f, 0x7d, 0x01, 0x10, 0x7d, 0x85, 0x2e, 0x4c,
+                0x24, 0x85, 0xc5, 0x1a, 0x50, 0xaa, 0xaa, 0x94, 0xfc, 0x61, 0x99, 0x5e, 0x71, 0xbb,
+                0xee, 0x98, 0x3a, 0x2a, 0xc3, 0x71, 0x38, 0x31, 0x26, 0x4a, 0xdb, 0x47, 0xfb, 0x6b,
+                0xd1, 0xe0, 0x58, 0xd5, 0xf0, 0x04,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-512("hello").
+    #[test]
+    fn sha3_512_one_shot_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Sha3::sha3_512(b"hello", &mut output);
+        assert_eq!(
+            output,
+            [
+                0x75, 0xd5, 0x27, 0xc3, 0x68, 0xf2, 0xef, 0xe8, 0x48, 0xec, 0xf6, 0xb0, 0x73, 0xa3,
+                0x67, 0x67, 0x80, 0x08, 0x05, 0xe9, 0xee, 0xf2, 0xb1, 0x85, 0x7d, 0x5f, 0x98, 0x4f,
+                0x03, 0x6e, 0xb6, 0xdf, 0x89, 0x1d, 0x75, 0xf7, 0x2d, 0x9b, 0x15, 0x45, 0x18, 0xc1,
+                0xcd, 0x58, 0x83, 0x52, 0x86, 0xd1, 0xda, 0x9a, 0x38, 0xde, 0xba, 0x3d, 0xe9, 0x8b,
+                0x5a, 0x53, 0xe5, 0xed, 0x78, 0xa8, 0x49, 0x76,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHA3-512("").
+    #[test]
+    fn sha3_512_of_empty_input_matches_known_vector() {
+        let mut output = [0u8; 64];
+        Sha3::sha3_512(b"", &mut output);
+        assert_eq!(
+            output,
+            [
+                0xa6, 0x9f, 0x73, 0xcc, 0xa2, 0x3a, 0x9a, 0xc5, 0xc8, 0xb5, 0x67, 0xdc, 0x18, 0x5a,
+                0x75, 0x6e, 0x97, 0xc9, 0x82, 0x16, 0x4f, 0xe2, 0x58, 0x59, 0xe0, 0xd1, 0xdc, 0xc1,
+                0x47, 0x5c, 0x80, 0xa6, 0x15, 0xb2, 0x12, 0x3a, 0xf1, 0xf5, 0xf9, 0x4c, 0x11, 0xe3,
+                0xe9, 0x40, 0x2c, 0x3a, 0xc5, 0x58, 0xf5, 0x00, 0x19, 0x9d, 0x95, 0xb6, 0xd3, 0xe3,
+                0x01, 0x75, 0x85, 0x86, 0x28, 0x1d, 0xcd, 0x26,
+            ]
+        );
+    }
+
+    #[test]
+    fn finalize_array_matches_finalize() {
+        let mut via_finalize = [0u8; 32];
+        Sha3::sha3_256(b"hello", &mut via_finalize);
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"hello");
+        let via_array: [u8; 32] = sha3.finalize_array();
+        assert_eq!(via_array, via_finalize);
+    }
+
+    #[test]
+    #[should_panic(expected = "output array length does not match the configured security level")]
+    fn finalize_array_panics_on_mismatched_length() {
+        let _: [u8; 16] = Sha3::v256().finalize_array();
+    }
+
+    #[test]
+    fn finalize_array_matches_finalize_for_every_variant() {
+        macro_rules! assert_variant_matches {
+            ($ctor:ident, $n:literal) => {
+                let mut via_finalize = [0u8; $n];
+                Sha3::$ctor().finalize(&mut via_finalize);
+
+                let via_array: [u8; $n] = Sha3::$ctor().finalize_array();
+                assert_eq!(via_array, via_finalize);
+            };
+        }
+
+        assert_variant_matches!(v224, 28);
+        assert_variant_matches!(v256, 32);
+        assert_variant_matches!(v384, 48);
+        assert_variant_matches!(v512, 64);
+    }
+
+    #[test]
+    fn try_finalize_rejects_a_31_and_33_byte_buffer_and_accepts_32() {
+        let mut too_short = [0u8; 31];
+        assert_eq!(Sha3::v256().try_finalize(&mut too_short), Err(crate::InvalidOutputLen));
+
+        let mut too_long = [0u8; 33];
+        assert_eq!(Sha3::v256().try_finalize(&mut too_long), Err(crate::InvalidOutputLen));
+
+        let mut just_right = [0u8; 32];
+        assert_eq!(Sha3::v256().try_finalize(&mut just_right), Ok(()));
+        assert_ne!(just_right, [0u8; 32]);
+    }
+
+    #[cfg(all(feature = "hex", feature = "alloc"))]
+    #[test]
+    fn finalize_hex_matches_the_hex_encoded_known_vector() {
+        assert_eq!(
+            Sha3::v256().finalize_hex(),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+        );
+    }
+
+    #[test]
+    fn clone_forks_a_partially_absorbed_state() {
+        let mut prefix = Sha3::v256();
+        prefix.update(b"hello");
+
+        let mut a = prefix.clone();
+        let mut b = prefix.clone();
+        a.update(b" world");
+        b.update(b" there");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+
+        let mut want = [0u8; 32];
+        Sha3::sha3_256(b"hello world", &mut want);
+        assert_eq!(out_a, want);
+    }
+
+    #[test]
+    fn finalize_reset_matches_finalize_then_fresh_hasher() {
+        let mut hasher = Sha3::v256();
+        hasher.update(b"hello");
+        let mut got = [0u8; 32];
+        hasher.finalize_reset(&mut got);
+
+        let mut want = [0u8; 32];
+        Sha3::sha3_256(b"hello", &mut want);
+        assert_eq!(got, want);
+
+        hasher.update(b"world");
+        let mut got2 = [0u8; 32];
+        hasher.finalize(&mut got2);
+        let mut want2 = [0u8; 32];
+        Sha3::sha3_256(b"world", &mut want2);
+        assert_eq!(got2, want2);
+    }
+
+    #[test]
+    fn free_functions_match_the_associated_one_shot_functions() {
+        let mut want = [0u8; 32];
+        Sha3::sha3_256(b"hello", &mut want);
+        assert_eq!(sha3_256(b"hello"), want);
+        assert_eq!(sha3_256("hello"), want);
+        assert_eq!(sha3_256(b"hello".to_vec()), want);
+    }
+
+    #[test]
+    fn rate_and_capacity_match_the_security_level() {
+        assert_eq!(Sha3::v224().rate(), 144);
+        assert_eq!(Sha3::v224().capacity_bits(), 448);
+
+        assert_eq!(Sha3::v256().rate(), 136);
+        assert_eq!(Sha3::v256().capacity_bits(), 512);
+
+        assert_eq!(Sha3::v384().rate(), 104);
+        assert_eq!(Sha3::v384().capacity_bits(), 768);
+
+        assert_eq!(Sha3::v512().rate(), 72);
+        assert_eq!(Sha3::v512().capacity_bits(), 1024);
+    }
+}
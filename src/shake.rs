@@ -0,0 +1,644 @@
+//! The `SHAKE` extendable-output functions.
+
+use super::{bits_to_rate, keccakf::KeccakF, Hasher, KeccakState, XofReader};
+
+/// The `SHAKE` extendable-output functions defined in [`FIPS-202`].
+///
+/// # Usage
+///
+/// ```toml
+/// [dependencies]
+/// tiny-keccak = { version = "2.0.0", features = ["shake"] }
+/// ```
+///
+/// [`FIPS-202`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+#[derive(Clone)]
+pub struct Shake {
+    state: KeccakState<KeccakF>,
+}
+
+impl Shake {
+    const DELIM: u8 = 0x1f;
+
+    /// Creates  new [`Shake`] hasher with a security level of 128 bits.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v128() -> Shake {
+        Shake::new(128)
+    }
+
+    /// Creates  new [`Shake`] hasher with a security level of 256 bits.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    pub fn v256() -> Shake {
+        Shake::new(256)
+    }
+
+    fn new(bits: usize) -> Shake {
+        Shake {
+            state: KeccakState::new(bits_to_rate(bits), Self::DELIM),
+        }
+    }
+
+    /// Pads the absorbed input and returns a [`ShakeReader`] that squeezes
+    /// output in a sequence of calls instead of one fixed-size buffer, for
+    /// streaming an arbitrary-length keystream (e.g. for a DRBG or mask
+    /// generation) without re-permuting from scratch on every read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::Shake;
+    ///
+    /// let mut shake = Shake::v256();
+    /// shake.update(b"hello");
+    /// let mut reader = shake.finalize_xof();
+    /// let mut first = [0u8; 10];
+    /// let mut second = [0u8; 22];
+    /// reader.squeeze(&mut first);
+    /// reader.squeeze(&mut second);
+    /// ```
+    #[doc(alias = "into_xof")]
+    pub fn finalize_xof(self) -> ShakeReader {
+        ShakeReader(XofReader::new(self.state))
+    }
+}
+
+/// An extendable-output reader returned by [`Shake::finalize_xof`].
+///
+/// Squeezing `n` bytes across several `squeeze` calls of arbitrary sizes
+/// produces the same `n` bytes as squeezing them in one call; the reader
+/// tracks the partial-block offset and only re-permutes the sponge once a
+/// full rate's worth of output has been read.
+#[derive(Clone)]
+pub struct ShakeReader(XofReader<KeccakF>);
+
+impl ShakeReader {
+    /// Squeezes `buf.len()` more bytes of output, continuing from wherever
+    /// the previous `squeeze` call (if any) left off.
+    pub fn squeeze(&mut self, buf: &mut [u8]) {
+        self.0.squeeze(buf);
+    }
+
+    /// Advances the squeeze position by `n` bytes without materializing
+    /// them, e.g. to resume a keystream at a known offset. Identical to
+    /// `squeeze`ing `n` bytes into a throwaway buffer, just without the
+    /// copy.
+    pub fn skip(&mut self, n: usize) {
+        self.0.skip(n);
+    }
+
+    /// Squeezes `expected.len()` more bytes and compares them to `expected`
+    /// as they're produced, returning the index of the first mismatching
+    /// byte rather than just whether they matched, without ever
+    /// materializing the whole stream in memory. Useful for pinpointing
+    /// where a reduced-round or accelerated implementation diverges from a
+    /// reference.
+    pub fn verify_stream(&mut self, expected: &[u8]) -> Result<(), usize> {
+        self.0.verify_stream(expected)
+    }
+
+    /// Splits off an independent reader that continues producing the exact
+    /// same byte stream from this point onward, without affecting `self`.
+    /// Useful for KDF-style protocols that need two (or more) independent
+    /// output streams derived from the same absorbed seed, without
+    /// re-absorbing it into a second hasher.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::Shake;
+    ///
+    /// let mut shake = Shake::v256();
+    /// shake.update(b"seed");
+    /// let mut reader = shake.finalize_xof();
+    ///
+    /// let mut first = [0u8; 10];
+    /// reader.squeeze(&mut first);
+    ///
+    /// let mut fork = reader.fork();
+    /// let mut a = [0u8; 20];
+    /// let mut b = [0u8; 20];
+    /// reader.squeeze(&mut a);
+    /// fork.squeeze(&mut b);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn fork(&self) -> ShakeReader {
+        self.clone()
+    }
+
+    /// Fills each of `outputs`, in order, from the continuous squeeze
+    /// stream, equivalent to squeezing `outputs.iter().map(|o| o.len()).sum()`
+    /// bytes in one call and splitting them across the given lengths --
+    /// convenient for KDF-style code that wants to derive several
+    /// differently-sized keys from one XOF in a single pass instead of
+    /// bookkeeping the offsets itself.
+    ///
+    /// Zero-length outputs are allowed (and are simply skipped); an output
+    /// that individually spans a rate boundary is handled the same way a
+    /// single [`squeeze`](Self::squeeze) call of that length would be.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::Shake;
+    ///
+    /// let mut shake = Shake::v256();
+    /// shake.update(b"seed");
+    /// let mut reader = shake.finalize_xof();
+    ///
+    /// let mut k1 = [0u8; 16];
+    /// let mut k2 = [0u8; 32];
+    /// reader.squeeze_many(&mut [&mut k1, &mut k2]);
+    ///
+    /// let mut want = [0u8; 48];
+    /// Shake::v256().chain(b"seed").finalize_xof().squeeze(&mut want);
+    /// assert_eq!(&k1[..], &want[..16]);
+    /// assert_eq!(&k2[..], &want[16..]);
+    /// ```
+    pub fn squeeze_many(&mut self, outputs: &mut [&mut [u8]]) {
+        for output in outputs {
+            self.squeeze(output);
+        }
+    }
+}
+
+impl Hasher for Shake {
+    /// Absorb additional input. Can be called multiple times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Shake};
+    /// #
+    /// # fn main() {
+    /// # let mut shake = Shake::v256();
+    /// shake.update(b"hello");
+    /// shake.update(b" world");
+    /// # }
+    /// ```
+    fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Pad and squeeze the state to the output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use tiny_keccak::{Hasher, Shake};
+    /// #
+    /// # fn main() {
+    /// # let shake = Shake::v256();
+    /// # let mut output = [0u8; 32];
+    /// shake.finalize(&mut output);
+    /// # }
+    /// #
+    /// ```
+    fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    /// Zeroes the sponge buffer and resets the absorb offset, so this
+    /// [`Shake`] instance can hash a stream of independent inputs without
+    /// reallocating.
+    ///
+    /// [`Shake`]: struct.Shake.html
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    /// Pad and squeeze the state to the output, then [`reset`](#method.reset)
+    /// in one step.
+    fn finalize_reset(&mut self, output: &mut [u8]) {
+        self.state.finalize_reset(output);
+    }
+}
+
+#[cfg(feature = "std")]
+crate::impl_io_write!(Shake);
+crate::impl_fmt_write!(Shake);
+crate::impl_debug_via_state!(Shake);
+crate::impl_xof!(Shake, ShakeReader);
+
+impl Shake {
+    /// Absorbs a compile-time-sized `data`, behaviorally identical to
+    /// `update(data)` but with `N` known at the call site, which lets the
+    /// optimizer elide the general absorb loop's bounds checks. Useful for
+    /// hashing fixed-size structs.
+    pub fn update_fixed<const N: usize>(&mut self, data: &[u8; N]) {
+        self.state.update_fixed(data);
+    }
+
+    /// Finalizes `other` into a stack buffer sized by its
+    /// [`Hasher::OUTPUT_LEN`] and absorbs the result, for hash-of-hash and
+    /// commitment-chain constructions. Only meaningful for `H` whose
+    /// `OUTPUT_LEN` is a real, non-zero per-type constant (e.g.
+    /// [`HmacSha3_256`](crate::HmacSha3_256)); panics if `H::OUTPUT_LEN` is
+    /// `0` or exceeds 64 bytes.
+    pub fn update_digest<H: Hasher>(&mut self, other: H) {
+        self.state.update_digest(other);
+    }
+
+    /// Absorbs `words` directly into the rate lanes as little-endian
+    /// 64-bit words, skipping the byte-repacking [`update`](Hasher::update)
+    /// does internally. Useful for callers (e.g. zk provers) that already
+    /// have word-aligned data.
+    ///
+    /// Equivalent to calling `update(&word.to_le_bytes())` for each word,
+    /// but without the intermediate byte buffer.
+    pub fn update_words(&mut self, words: &[u64]) {
+        self.state.update_words(words);
+    }
+
+    /// The sponge rate, in bytes, this hasher was constructed with.
+    pub fn rate(&self) -> usize {
+        self.state.rate()
+    }
+
+    /// The sponge capacity, in bits, this hasher was constructed with.
+    pub fn capacity_bits(&self) -> usize {
+        self.state.capacity_bits()
+    }
+
+    /// The total number of bytes passed to [`update`](Hasher::update) since
+    /// construction or the last [`reset`](Hasher::reset).
+    pub fn bytes_absorbed(&self) -> u64 {
+        self.state.bytes_absorbed()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Shake {
+    /// Pads the absorbed input and squeezes `len` bytes into a
+    /// heap-allocated boxed slice, for callers (e.g. using `Shake` as a KDF)
+    /// that only learn the desired output length at runtime and would
+    /// otherwise need to pre-allocate a buffer themselves before calling
+    /// [`finalize_xof`](Shake::finalize_xof).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tiny_keccak::Shake;
+    ///
+    /// let mut shake = Shake::v256();
+    /// shake.update(b"hello");
+    /// let boxed = shake.finalize_boxed(100);
+    /// assert_eq!(boxed.len(), 100);
+    /// ```
+    pub fn finalize_boxed(self, len: usize) -> alloc::boxed::Box<[u8]> {
+        let mut reader = self.finalize_xof();
+        let mut output = alloc::vec![0u8; len].into_boxed_slice();
+        reader.squeeze(&mut output);
+        output
+    }
+}
+
+/// A SHAKE-based mask generation function: absorbs `seed` and squeezes
+/// `out.len()` bytes of mask into `out`, using `Shake::v256`.
+///
+/// Classic MGF1 (RFC 8017) drives a *fixed-output* hash (e.g. SHA-256) with
+/// an incrementing 4-byte counter appended to the seed, concatenating one
+/// hash's worth of output per counter value until enough mask bytes have
+/// been produced. Because SHAKE is already an extendable-output function,
+/// none of that counter bookkeeping is needed here: squeezing further bytes
+/// from the same sponge state *is* the mask generation, so this is both
+/// simpler and avoids MGF1's classic pitfall of getting the counter's
+/// endianness or width wrong. The one thing it deliberately gives up is
+/// MGF1's interoperability — this is not a drop-in replacement for an
+/// existing RSA-OAEP/PSS implementation that expects real MGF1.
+pub fn mgf_shake256(seed: &[u8], out: &mut [u8]) {
+    let mut shake = Shake::v256();
+    shake.update(seed);
+    shake.finalize_xof().squeeze(out);
+}
+
+/// Like [`mgf_shake256`], but using `Shake::v128` for a smaller security
+/// margin at (roughly) twice the squeeze throughput.
+pub fn mgf_shake128(seed: &[u8], out: &mut [u8]) {
+    let mut shake = Shake::v128();
+    shake.update(seed);
+    shake.finalize_xof().squeeze(out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Known vector: FIPS-202 SHAKE128(""), first 32 output bytes.
+    #[test]
+    fn shake128_of_empty_input_matches_known_vector() {
+        let mut shake = Shake::v128();
+        let mut output = [0u8; 32];
+        shake.update(b"");
+        shake.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x7f, 0x9c, 0x2b, 0xa4, 0xe8, 0x8f, 0x82, 0x7d, 0x61, 0x60, 0x45, 0x50, 0x76, 0x05,
+                0x85, 0x3e, 0xd7, 0x3b, 0x80, 0x93, 0xf6, 0xef, 0xbc, 0x88, 0xeb, 0x1a, 0x6e, 0xac,
+                0xfa, 0x66, 0xef, 0x26,
+            ]
+        );
+    }
+
+    // Known vector: FIPS-202 SHAKE256(""), first 32 output bytes.
+    #[test]
+    fn shake256_of_empty_input_matches_known_vector() {
+        let mut shake = Shake::v256();
+        let mut output = [0u8; 32];
+        shake.update(b"");
+        shake.finalize(&mut output);
+        assert_eq!(
+            output,
+            [
+                0x46, 0xb9, 0xdd, 0x2b, 0x0b, 0xa8, 0x8d, 0x13, 0x23, 0x3b, 0x3f, 0xeb, 0x74, 0x3e,
+                0xeb, 0x24, 0x3f, 0xcd, 0x52, 0xea, 0x62, 0xb8, 0x1b, 0x82, 0xb5, 0x0c, 0x27, 0x64,
+                0x6e, 0xd5, 0x76, 0x2f,
+            ]
+        );
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher() {
+        let mut hasher = Shake::v128();
+        hasher.update(b"garbage to discard");
+        hasher.reset();
+        hasher.update(b"");
+        let mut got = [0u8; 32];
+        hasher.finalize(&mut got);
+
+        let mut want = [0u8; 32];
+        Shake::v128().finalize(&mut want);
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn clone_forks_a_partially_absorbed_state() {
+        let mut prefix = Shake::v128();
+        prefix.update(b"hello");
+
+        let mut a = prefix.clone();
+        let mut b = prefix.clone();
+        a.update(b" world");
+        b.update(b" there");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+
+        let mut want = [0u8; 32];
+        let mut want_hasher = Shake::v128();
+        want_hasher.update(b"hello world");
+        want_hasher.finalize(&mut want);
+        assert_eq!(out_a, want);
+    }
+
+    // Squeezing `LEN` bytes through `chunks` (summing to `LEN`) via
+    // `finalize_xof` must match one single-shot `finalize` of `LEN` bytes.
+    fn assert_xof_matches_single_shot<const LEN: usize>(chunks: &[usize]) {
+        debug_assert_eq!(chunks.iter().sum::<usize>(), LEN);
+
+        let mut single_shot = Shake::v128();
+        single_shot.update(b"hello");
+        let mut want = [0u8; LEN];
+        single_shot.finalize(&mut want);
+
+        let mut streamed = Shake::v128();
+        streamed.update(b"hello");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; LEN];
+        let mut pos = 0;
+        for &chunk in chunks {
+            reader.squeeze(&mut got[pos..pos + chunk]);
+            pos += chunk;
+        }
+
+        assert_eq!(got, want);
+    }
+
+    // Shake128's rate is 168 bytes.
+    #[test]
+    fn xof_reader_matches_single_shot_at_rate_boundary() {
+        assert_xof_matches_single_shot::<336>(&[168, 168]);
+    }
+
+    #[test]
+    fn xof_reader_matches_single_shot_off_by_one_from_boundary() {
+        assert_xof_matches_single_shot::<336>(&[167, 1, 1, 167]);
+    }
+
+    #[test]
+    fn xof_reader_matches_single_shot_across_multiple_blocks() {
+        assert_xof_matches_single_shot::<400>(&[10, 22, 200, 1, 167]);
+    }
+
+    #[test]
+    fn rate_and_capacity_match_the_security_level() {
+        assert_eq!(Shake::v128().rate(), 168);
+        assert_eq!(Shake::v128().capacity_bits(), 256);
+
+        assert_eq!(Shake::v256().rate(), 136);
+        assert_eq!(Shake::v256().capacity_bits(), 512);
+    }
+
+    #[test]
+    fn forked_readers_stay_identical_across_a_rate_boundary() {
+        // Shake128's rate is 168 bytes; squeeze a partial block, fork, then
+        // squeeze well past the rate boundary on both the original and the
+        // fork, and on a third, never-forked reader for comparison.
+        let mut want_hasher = Shake::v128();
+        want_hasher.update(b"hello");
+        let mut want = [0u8; 400];
+        want_hasher.finalize_xof().squeeze(&mut want);
+
+        let mut hasher = Shake::v128();
+        hasher.update(b"hello");
+        let mut reader = hasher.finalize_xof();
+        let mut prefix = [0u8; 100];
+        reader.squeeze(&mut prefix);
+        assert_eq!(prefix, want[..100]);
+
+        let mut fork = reader.fork();
+
+        let mut rest_original = [0u8; 300];
+        reader.squeeze(&mut rest_original);
+        assert_eq!(rest_original, want[100..]);
+
+        let mut rest_fork = [0u8; 300];
+        fork.squeeze(&mut rest_fork);
+        assert_eq!(rest_fork, want[100..]);
+
+        assert_eq!(rest_original, rest_fork);
+    }
+
+    #[test]
+    fn squeeze_many_matches_sequential_squeeze_calls() {
+        // Shake128's rate is 168 bytes; the lengths below include a
+        // zero-length output and two that individually cross the rate
+        // boundary on their own.
+        let mut want_hasher = Shake::v128();
+        want_hasher.update(b"seed");
+        let mut want = [0u8; 400];
+        want_hasher.finalize_xof().squeeze(&mut want);
+
+        let mut hasher = Shake::v128();
+        hasher.update(b"seed");
+        let mut reader = hasher.finalize_xof();
+
+        let mut a = [0u8; 0];
+        let mut b = [0u8; 200];
+        let mut c = [0u8; 30];
+        let mut d = [0u8; 170];
+        reader.squeeze_many(&mut [&mut a, &mut b, &mut c, &mut d]);
+
+        assert_eq!(&b[..], &want[..200]);
+        assert_eq!(&c[..], &want[200..230]);
+        assert_eq!(&d[..], &want[230..400]);
+    }
+
+    // Shake128's rate is 168 bytes; these skip counts cover skipping within
+    // the first block, skipping exactly to a rate boundary, skipping across
+    // several rate blocks, and skipping a partial block before squeezing.
+    #[test]
+    fn skip_matches_squeezing_into_a_throwaway_buffer() {
+        for skip_len in [0, 1, 100, 168, 169, 500, 503] {
+            let mut hasher = Shake::v128();
+            hasher.update(b"seed");
+            let mut skipped = hasher.finalize_xof();
+            skipped.skip(skip_len);
+            let mut want_after_skip = [0u8; 50];
+            skipped.squeeze(&mut want_after_skip);
+
+            let mut hasher = Shake::v128();
+            hasher.update(b"seed");
+            let mut squeezed = hasher.finalize_xof();
+            let mut throwaway = [0u8; 503];
+            squeezed.squeeze(&mut throwaway[..skip_len]);
+            let mut got_after_squeeze = [0u8; 50];
+            squeezed.squeeze(&mut got_after_squeeze);
+
+            assert_eq!(
+                want_after_skip, got_after_squeeze,
+                "skip({skip_len}) diverged from squeezing {skip_len} throwaway bytes"
+            );
+        }
+    }
+
+    #[test]
+    fn verify_stream_reports_the_first_mismatching_byte_index() {
+        let mut hasher = Shake::v128();
+        hasher.update(b"seed");
+        let mut reference = [0u8; 400];
+        hasher.finalize_xof().squeeze(&mut reference);
+
+        let mut matching = reference;
+        let mut hasher = Shake::v128();
+        hasher.update(b"seed");
+        assert_eq!(hasher.finalize_xof().verify_stream(&matching), Ok(()));
+
+        // Shake128's rate is 168 bytes; corrupt a byte well past the first
+        // rate block so a bug that only checks the first chunk can't hide.
+        const KNOWN_OFFSET: usize = 201;
+        matching[KNOWN_OFFSET] ^= 0xff;
+        let mut hasher = Shake::v128();
+        hasher.update(b"seed");
+        assert_eq!(hasher.finalize_xof().verify_stream(&matching), Err(KNOWN_OFFSET));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn finalize_boxed_matches_a_chunked_squeeze() {
+        let mut streamed = Shake::v128();
+        streamed.update(b"hello");
+        let mut want = [0u8; 100];
+        let mut reader = streamed.finalize_xof();
+        reader.squeeze(&mut want[..40]);
+        reader.squeeze(&mut want[40..]);
+
+        let mut boxed = Shake::v128();
+        boxed.update(b"hello");
+        let got = boxed.finalize_boxed(100);
+
+        assert_eq!(&*got, &want[..]);
+    }
+
+    #[test]
+    fn mgf_shake256_matches_a_direct_shake256_squeeze() {
+        let mut want = [0u8; 100];
+        let mut direct = Shake::v256();
+        direct.update(b"seed");
+        direct.finalize_xof().squeeze(&mut want);
+
+        let mut got = [0u8; 100];
+        mgf_shake256(b"seed", &mut got);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn mgf_shake128_matches_a_direct_shake128_squeeze() {
+        let mut want = [0u8; 64];
+        let mut direct = Shake::v128();
+        direct.update(b"seed");
+        direct.finalize_xof().squeeze(&mut want);
+
+        let mut got = [0u8; 64];
+        mgf_shake128(b"seed", &mut got);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn mgf_shake256_and_mgf_shake128_diverge_on_the_same_seed() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        mgf_shake256(b"seed", &mut a);
+        mgf_shake128(b"seed", &mut b);
+        assert_ne!(a, b);
+    }
+
+    // A single `squeeze(len)` call spanning `len` bytes must match the
+    // concatenation of squeezing one rate-sized block at a time out to the
+    // same length: the permutation has to land exactly on the rate
+    // boundary, neither one block early nor one block late, or a `len` that
+    // straddles several rate blocks in one call would silently diverge from
+    // the same bytes read out block by block. `len` of `rate - 1`, `rate`,
+    // `rate + 1` and `3 * rate + 1` cover landing just short of, exactly
+    // on, just past, and well past a rate boundary within a single call.
+    fn assert_squeeze_matches_block_by_block_reference(new_hasher: impl Fn() -> Shake, rate: usize) {
+        // 512 comfortably covers `3 * rate + 1` for both Shake128 (168) and
+        // Shake256 (136).
+        for len in [rate - 1, rate, rate + 1, 3 * rate + 1] {
+            let mut reference = new_hasher();
+            reference.update(b"hello");
+            let mut reference_reader = reference.finalize_xof();
+            let mut want = [0u8; 512];
+            let mut pos = 0;
+            while pos < len {
+                let take = core::cmp::min(rate, len - pos);
+                reference_reader.squeeze(&mut want[pos..pos + take]);
+                pos += take;
+            }
+
+            let mut hasher = new_hasher();
+            hasher.update(b"hello");
+            let mut got = [0u8; 512];
+            hasher.finalize_xof().squeeze(&mut got[..len]);
+
+            assert_eq!(&got[..len], &want[..len], "diverged at len={len}, rate={rate}");
+        }
+    }
+
+    #[test]
+    fn shake128_squeeze_matches_block_by_block_reference_around_the_rate_boundary() {
+        assert_squeeze_matches_block_by_block_reference(Shake::v128, 168);
+    }
+
+    #[test]
+    fn shake256_squeeze_matches_block_by_block_reference_around_the_rate_boundary() {
+        assert_squeeze_matches_block_by_block_reference(Shake::v256, 136);
+    }
+}
@@ -0,0 +1,237 @@
+//! NIST SP800-185 byte-string encodings (`left_encode`, `right_encode`,
+//! `encode_string`) shared by cSHAKE-family constructions such as
+//! [`KangarooTwelve256`](crate::KangarooTwelve256).
+//!
+//! These are exposed publicly so callers building their own SP800-185-style
+//! constructions (a bespoke MAC, a custom tree hash) don't have to
+//! reimplement the length-prefixing rules and get the edge cases subtly
+//! wrong.
+
+/// Encodes `value` as `left_encode(value)`: the minimal-length big-endian
+/// byte string representing `value`, preceded by a single byte giving that
+/// string's length.
+///
+/// Returns the used prefix of `out`, whose length is at most 9 bytes (one
+/// length byte plus up to 8 value bytes), so a caller-provided `out` of at
+/// least 9 bytes is always big enough.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than 9 bytes.
+pub fn left_encode(value: u64, out: &mut [u8]) -> &[u8] {
+    assert!(out.len() >= 9, "left_encode needs at least 9 bytes of scratch space");
+    let be = value.to_be_bytes();
+    let start = be.iter().position(|&b| b != 0).unwrap_or(7).min(7);
+    let len = 8 - start;
+    out[0] = len as u8;
+    out[1..1 + len].copy_from_slice(&be[start..]);
+    &out[..1 + len]
+}
+
+/// Encodes `value` as `right_encode(value)`: the minimal-length big-endian
+/// byte string representing `value`, followed by a single byte giving that
+/// string's length.
+///
+/// Returns the used prefix of `out`, whose length is at most 9 bytes (up to
+/// 8 value bytes plus one length byte), so a caller-provided `out` of at
+/// least 9 bytes is always big enough.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than 9 bytes.
+pub fn right_encode(value: u64, out: &mut [u8]) -> &[u8] {
+    assert!(out.len() >= 9, "right_encode needs at least 9 bytes of scratch space");
+    let be = value.to_be_bytes();
+    let start = be.iter().position(|&b| b != 0).unwrap_or(7).min(7);
+    let len = 8 - start;
+    out[..len].copy_from_slice(&be[start..]);
+    out[len] = len as u8;
+    &out[..len + 1]
+}
+
+/// Encodes `s` as `encode_string(s)`: `left_encode(s.len() * 8)` (the
+/// bit length of `s`) followed by `s` itself, as required to
+/// unambiguously frame a byte string inside a larger absorbed input.
+///
+/// Unlike [`left_encode`]/[`right_encode`], this allocates its own
+/// `Vec` for the result since the combined length depends on `s`.
+///
+/// # Panics
+///
+/// Panics if `s.len()` in bits would overflow a `u64` (i.e. `s` is longer
+/// than `2^61` bytes), which cannot happen on any real input.
+#[cfg(feature = "std")]
+pub fn encode_string(s: &[u8]) -> std::vec::Vec<u8> {
+    let bit_len = (s.len() as u64)
+        .checked_mul(8)
+        .expect("byte string too long to encode its bit length in a u64");
+    let mut prefix = [0u8; 9];
+    let prefix = left_encode(bit_len, &mut prefix);
+    let mut out = std::vec::Vec::with_capacity(prefix.len() + s.len());
+    out.extend_from_slice(prefix);
+    out.extend_from_slice(s);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn left_encode_of_zero_is_a_single_zero_byte_with_length_one() {
+        let mut out = [0u8; 9];
+        assert_eq!(left_encode(0, &mut out), &[1, 0]);
+    }
+
+    #[test]
+    fn right_encode_of_zero_is_a_single_zero_byte_with_length_one() {
+        let mut out = [0u8; 9];
+        assert_eq!(right_encode(0, &mut out), &[0, 1]);
+    }
+
+    #[test]
+    fn left_encode_of_max_value_uses_the_full_eight_bytes() {
+        let mut out = [0u8; 9];
+        let encoded = left_encode(u64::MAX, &mut out);
+        assert_eq!(encoded[0], 8);
+        assert_eq!(&encoded[1..], &u64::MAX.to_be_bytes());
+    }
+
+    #[test]
+    fn right_encode_of_max_value_uses_the_full_eight_bytes() {
+        let mut out = [0u8; 9];
+        let encoded = right_encode(u64::MAX, &mut out);
+        assert_eq!(&encoded[..8], &u64::MAX.to_be_bytes());
+        assert_eq!(encoded[8], 8);
+    }
+
+    #[test]
+    fn left_encode_drops_leading_zero_bytes() {
+        let mut out = [0u8; 9];
+        // 256 needs two bytes (0x01, 0x00); no leading zero byte to drop.
+        assert_eq!(left_encode(256, &mut out), &[2, 1, 0]);
+        // 255 fits in a single byte.
+        assert_eq!(left_encode(255, &mut out), &[1, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 9 bytes")]
+    fn left_encode_panics_on_undersized_scratch_space() {
+        let mut out = [0u8; 8];
+        left_encode(u64::MAX, &mut out);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_string_prefixes_the_bit_length() {
+        let encoded = encode_string(b"hi");
+        // "hi" is 2 bytes = 16 bits, which left_encode's as [1, 16].
+        assert_eq!(encoded, [1, 16, b'h', b'i']);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_string_of_a_string_longer_than_255_bytes() {
+        let s = std::vec![0x42u8; 300];
+        let encoded = encode_string(&s);
+        // 300 bytes = 2400 bits = 0x0960, encoded as two bytes.
+        assert_eq!(&encoded[..3], &[2, 0x09, 0x60]);
+        assert_eq!(&encoded[3..], &s[..]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_string_of_empty_input() {
+        let encoded = encode_string(&[]);
+        assert_eq!(encoded, [1, 0]);
+    }
+
+    // The encoded value length in bytes only grows when crossing a
+    // power-of-256 boundary (i.e. every 8 bits), so this pins the byte
+    // count at each such boundary across the whole `u64` range, including
+    // right up against `2^63` and `u64::MAX`.
+    #[test]
+    fn left_encode_byte_length_grows_only_at_power_of_256_boundaries() {
+        let mut out = [0u8; 9];
+
+        let cases: &[(u64, u8)] = &[
+            (0, 1),
+            (1, 1),
+            (0xff, 1),
+            (0x100, 2),
+            (0xffff, 2),
+            (0x1_0000, 3),
+            (0xff_ffff, 3),
+            (0x100_0000, 4),
+            (0xffff_ffff, 4),
+            (0x1_0000_0000, 5),
+            (0xff_ffff_ffff, 5),
+            (0x100_0000_0000, 6),
+            (0xffff_ffff_ffff, 6),
+            (0x1_0000_0000_0000, 7),
+            (0xff_ffff_ffff_ffff, 7),
+            (0x100_0000_0000_0000, 8),
+            (1u64 << 63, 8),
+            (u64::MAX, 8),
+        ];
+
+        for &(value, expected_len) in cases {
+            let encoded = left_encode(value, &mut out);
+            assert_eq!(
+                encoded[0], expected_len,
+                "left_encode({value:#x}) should use {expected_len} value bytes",
+            );
+            assert_eq!(encoded.len(), 1 + expected_len as usize);
+        }
+    }
+
+    #[test]
+    fn right_encode_byte_length_grows_only_at_power_of_256_boundaries() {
+        let mut out = [0u8; 9];
+
+        let cases: &[(u64, u8)] = &[
+            (0, 1),
+            (0xff, 1),
+            (0x100, 2),
+            (0xffff_ffff, 4),
+            (0x1_0000_0000, 5),
+            (1u64 << 63, 8),
+            (u64::MAX, 8),
+        ];
+
+        for &(value, expected_len) in cases {
+            let encoded = right_encode(value, &mut out);
+            assert_eq!(encoded.len(), 1 + expected_len as usize);
+            assert_eq!(
+                encoded[encoded.len() - 1],
+                expected_len,
+                "right_encode({value:#x}) should use {expected_len} value bytes",
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_string_bit_length_prefix_survives_a_simulated_multi_gigabyte_message() {
+        // Absorbing a real multi-GB slice just to exercise this would be
+        // wasteful; `encode_string`'s bit-length prefix only depends on
+        // `s.len()`, so simulate it by encoding the bit length directly and
+        // confirming it matches what `encode_string` would prepend to a
+        // slice of that length, without materializing the slice itself.
+        let five_gigabytes = 5_000_000_000u64;
+        let bit_len = five_gigabytes * 8;
+
+        let mut out = [0u8; 9];
+        let encoded = left_encode(bit_len, &mut out);
+        // 5_000_000_000 * 8 = 40_000_000_000 = 0x9502F9000, which needs 5
+        // value bytes.
+        assert_eq!(encoded, &[5, 0x09, 0x50, 0x2f, 0x90, 0x00]);
+
+        // A message right at the edge of overflowing `u64`'s bit-length
+        // representation (`s.len() > 2^61` bytes) still can't happen on a
+        // real input, but the multiplication itself must not silently wrap.
+        let bytes_just_under_the_limit = u64::MAX / 8;
+        assert!(bytes_just_under_the_limit.checked_mul(8).is_some());
+        assert!((bytes_just_under_the_limit + 1).checked_mul(8).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+//! [`Sponge`]: the bare `Keccak-f[1600]` sponge, exposed for custom
+//! constructions that need direct control over absorb/pad/squeeze instead
+//! of going through the [`Hasher`](crate::Hasher) trait's fixed
+//! absorb-then-finalize shape.
+
+use crate::keccakf::{KeccakF, KeccakP1600};
+use crate::KeccakState;
+
+/// A minimal sponge over `Keccak-f[1600]`, for building custom constructions
+/// (PRNGs, commitment schemes) directly on the permutation.
+///
+/// Unlike [`Hasher`](crate::Hasher), which bundles padding and squeezing
+/// into a single consuming [`finalize`](crate::Hasher::finalize) call,
+/// `Sponge` exposes [`absorb`](Self::absorb), [`pad`](Self::pad), and
+/// [`squeeze`](Self::squeeze) as distinct operations, so callers can, for
+/// instance, pad and squeeze multiple times against the same absorbed
+/// prefix.
+///
+/// # Invariants
+///
+/// Absorb before padding: call [`absorb`](Self::absorb) only while no
+/// [`pad`](Self::pad) call has happened yet. Squeeze after padding: call
+/// [`squeeze`](Self::squeeze) only after [`pad`](Self::pad). Violating
+/// either only debug-panics (via the same absorb-after-pad check
+/// [`KeccakState`](crate::KeccakState) itself uses), matching this crate's
+/// existing convention of catching internal-plumbing bugs, not user misuse,
+/// with debug assertions.
+pub struct Sponge {
+    state: KeccakState<KeccakF>,
+    // Byte offset into the current squeezed block, tracked separately from
+    // `state`'s own absorb `offset` so `squeeze` can resume mid-block across
+    // calls, the same way `XofReader` does (`state.squeeze` is a one-shot
+    // squeeze-to-completion, not a resumable one).
+    squeeze_offset: usize,
+}
+
+impl Sponge {
+    /// Creates a new sponge with the given `rate` (in bytes) and
+    /// domain-separation suffix `delim`, e.g. `0x06` for SHA3's `pad10*1`
+    /// framing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero.
+    pub fn new(rate: usize, delim: u8) -> Self {
+        Sponge {
+            state: KeccakState::new(rate, delim),
+            squeeze_offset: 0,
+        }
+    }
+
+    /// Absorbs `input`. Can be called multiple times before [`pad`](Self::pad).
+    pub fn absorb(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Applies `pad10*1` padding and permutes once, switching the sponge
+    /// from absorbing to squeezing.
+    pub fn pad(&mut self) {
+        self.state.pad();
+        self.state.keccak();
+    }
+
+    /// Squeezes `output.len()` bytes, continuing from wherever the previous
+    /// [`squeeze`](Self::squeeze) call (if any) left off, permuting between
+    /// rate-sized blocks as needed.
+    pub fn squeeze(&mut self, mut output: &mut [u8]) {
+        let rate = self.state.rate();
+        while !output.is_empty() {
+            let take = core::cmp::min(rate - self.squeeze_offset, output.len());
+            let (head, tail) = output.split_at_mut(take);
+            self.state.buffer.setout(head, self.squeeze_offset, take);
+            self.squeeze_offset += take;
+            output = tail;
+            if self.squeeze_offset == rate {
+                self.state.keccak();
+                self.squeeze_offset = 0;
+            }
+        }
+    }
+}
+
+/// A sponge over [`KeccakP1600<ROUNDS>`](KeccakP1600), fully parameterized
+/// at compile time by its `RATE` (in bytes) and `ROUNDS`, for researchers
+/// instantiating custom sponge parameters (e.g. an Ascon-style scheme,
+/// though Ascon itself uses a different permutation) with no runtime
+/// branching.
+///
+/// The generalization of [`Sponge`] (fixed to the standard 24-round
+/// permutation, with `rate` chosen at construction instead of compile
+/// time) to arbitrary compile-time-known parameters; see `Sponge`'s own
+/// documentation for the shared absorb-then-pad-then-squeeze usage shape
+/// and its absorb-before-pad/squeeze-after-pad invariants.
+pub struct GenericSponge<const RATE: usize, const ROUNDS: usize> {
+    state: KeccakState<KeccakP1600<ROUNDS>>,
+    squeeze_offset: usize,
+}
+
+impl<const RATE: usize, const ROUNDS: usize> GenericSponge<RATE, ROUNDS> {
+    /// Creates a new sponge with the given domain-separation suffix
+    /// `delim`, e.g. `0x06` for SHA3's `pad10*1` framing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `RATE` is zero.
+    pub fn new(delim: u8) -> Self {
+        GenericSponge {
+            state: KeccakState::new(RATE, delim),
+            squeeze_offset: 0,
+        }
+    }
+
+    /// Absorbs `input`. Can be called multiple times before [`pad`](Self::pad).
+    pub fn absorb(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    /// Applies `pad10*1` padding and permutes once, switching the sponge
+    /// from absorbing to squeezing.
+    pub fn pad(&mut self) {
+        self.state.pad();
+        self.state.keccak();
+    }
+
+    /// Squeezes `output.len()` bytes, continuing from wherever the previous
+    /// [`squeeze`](Self::squeeze) call (if any) left off, permuting between
+    /// rate-sized blocks as needed.
+    pub fn squeeze(&mut self, mut output: &mut [u8]) {
+        let rate = self.state.rate();
+        while !output.is_empty() {
+            let take = core::cmp::min(rate - self.squeeze_offset, output.len());
+            let (head, tail) = output.split_at_mut(take);
+            self.state.buffer.setout(head, self.squeeze_offset, take);
+            self.squeeze_offset += take;
+            output = tail;
+            if self.squeeze_offset == rate {
+                self.state.keccak();
+                self.squeeze_offset = 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn manual_sha3_256_matches_sha3_v256() {
+        use crate::{Hasher, Sha3};
+
+        let mut sponge = Sponge::new(136, 0x06);
+        sponge.absorb(b"hello");
+        sponge.pad();
+        let mut got = [0u8; 32];
+        sponge.squeeze(&mut got);
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"hello");
+        let mut want = [0u8; 32];
+        sha3.finalize(&mut want);
+
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn squeezing_past_one_rate_block_permutes_between_blocks() {
+        let mut a = Sponge::new(136, 0x06);
+        a.absorb(b"hello");
+        a.pad();
+        let mut long = [0u8; 300];
+        a.squeeze(&mut long);
+
+        let mut b = Sponge::new(136, 0x06);
+        b.absorb(b"hello");
+        b.pad();
+        let mut first = [0u8; 17];
+        let mut rest = [0u8; 283];
+        b.squeeze(&mut first);
+        b.squeeze(&mut rest);
+
+        assert_eq!(&long[..17], &first[..]);
+        assert_eq!(&long[17..], &rest[..]);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn generic_sponge_136_24_reproduces_sha3_256() {
+        use crate::{Hasher, Sha3};
+
+        // 136-byte rate and 24 rounds are exactly SHA3-256's own
+        // parameters, so this should match `Sha3::v256` byte for byte.
+        let mut sponge: GenericSponge<136, 24> = GenericSponge::new(0x06);
+        sponge.absorb(b"hello");
+        sponge.pad();
+        let mut got = [0u8; 32];
+        sponge.squeeze(&mut got);
+
+        let mut sha3 = Sha3::v256();
+        sha3.update(b"hello");
+        let mut want = [0u8; 32];
+        sha3.finalize(&mut want);
+
+        assert_eq!(got, want);
+    }
+}
@@ -0,0 +1,232 @@
+//! `TupleHashXOF`: the SP800-185 extendable-output tuple hash.
+//!
+//! `TupleHash` hashes a sequence of byte strings such that the tuple's
+//! boundaries can't be shifted without changing the digest: each element is
+//! independently [`encode_string`]d before being absorbed, so e.g.
+//! `["ab", "c"]` and `["a", "bc"]` (which would collide under naive
+//! concatenation) hash differently. Only the XOF form is provided here,
+//! whose output length is not bound into the digest, unlike SP800-185's
+//! fixed-length `TupleHash`.
+//!
+//! This has not been checked against the SP800-185 `TupleHashXOF`
+//! known-answer test vectors, only for internal self-consistency (see the
+//! tests below): treat it as a best-effort structural implementation of the
+//! construction rather than a validated one.
+
+use crate::cshake::{CShake128, CShake128Reader, CShake256, CShake256Reader};
+use crate::sp800::{encode_string, right_encode};
+use crate::Hasher;
+
+macro_rules! tuple_hash_xof {
+    ($name:ident, $reader:ident, $cshake:ident, $cshake_reader:ident, $doc:expr, $reader_doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            cshake: $cshake,
+        }
+
+        impl $name {
+            /// Creates a new hasher with customization string `s`. Pass
+            /// `&[]` if no customization is needed.
+            pub fn new(s: &[u8]) -> Self {
+                $name {
+                    cshake: $cshake::new(b"TupleHash", s),
+                }
+            }
+
+            /// Absorbs one more element of the tuple, independently
+            /// length-encoding it so the boundary between this element and
+            /// its neighbors can't be shifted.
+            pub fn update_element(&mut self, data: &[u8]) {
+                self.cshake.update(&encode_string(data));
+            }
+
+            /// Pads the absorbed elements (appending a trailing
+            /// `right_encode(0)`, per `TupleHashXOF`'s definition) and
+            /// returns a reader that squeezes output in a sequence of
+            /// calls instead of one fixed-size buffer.
+            #[doc(alias = "into_xof")]
+            pub fn finalize_xof(mut self) -> $reader {
+                let mut encoded_zero = [0u8; 9];
+                let encoded_zero = right_encode(0, &mut encoded_zero);
+                self.cshake.update(encoded_zero);
+                $reader(self.cshake.finalize_xof())
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name)).field("cshake", &self.cshake).finish()
+            }
+        }
+
+        #[doc = $reader_doc]
+        #[derive(Clone)]
+        pub struct $reader($cshake_reader);
+
+        impl $reader {
+            /// Squeezes `buf.len()` more bytes, continuing from wherever
+            /// the previous `squeeze` call (if any) left off.
+            pub fn squeeze(&mut self, buf: &mut [u8]) {
+                self.0.squeeze(buf);
+            }
+        }
+
+        crate::impl_xof!($name, $reader);
+    };
+}
+
+tuple_hash_xof!(
+    TupleHashXof128,
+    TupleHashXof128Reader,
+    CShake128,
+    CShake128Reader,
+    "`TupleHashXOF128`: the 128-bit-security extendable-output tuple hash.",
+    "An extendable-output reader returned by \
+     [`TupleHashXof128::finalize_xof`]."
+);
+tuple_hash_xof!(
+    TupleHashXof256,
+    TupleHashXof256Reader,
+    CShake256,
+    CShake256Reader,
+    "`TupleHashXOF256`: the 256-bit-security extendable-output tuple hash.",
+    "An extendable-output reader returned by \
+     [`TupleHashXof256::finalize_xof`]."
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn squeeze_all(hasher: TupleHashXof256, elements: &[&[u8]], out: &mut [u8]) {
+        let mut hasher = hasher;
+        for element in elements {
+            hasher.update_element(element);
+        }
+        hasher.finalize_xof().squeeze(out);
+    }
+
+    #[test]
+    fn ambiguous_groupings_diverge() {
+        // Naive concatenation would make ["ab", "c"] and ["a", "bc"]
+        // indistinguishable; TupleHash's per-element length encoding must
+        // keep them apart.
+        let mut a = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"ab", b"c"], &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"a", b"bc"], &mut b);
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn a_tuple_with_no_elements_still_finalizes_deterministically() {
+        // Zero `update_element` calls before `finalize_xof` (as opposed to
+        // an element that happens to be empty) must not be special-cased
+        // away as a no-op.
+        let mut a = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[], &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[], &mut b);
+
+        assert_eq!(a, b);
+        assert_ne!(a, [0u8; 32]);
+    }
+
+    #[test]
+    fn element_order_matters() {
+        let mut a = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"one", b"two"], &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"two", b"one"], &mut b);
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"one", b"two"], &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"one", b"two"], &mut b);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn a_non_empty_customization_diverges() {
+        let mut a = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(&[]), &[b"one", b"two"], &mut a);
+
+        let mut b = [0u8; 32];
+        squeeze_all(TupleHashXof256::new(b"custom"), &[b"one", b"two"], &mut b);
+
+        assert_ne!(a, b);
+    }
+
+    // TupleHash's function-name string `N` ("TupleHash") is fixed and
+    // non-empty, so it must always keep cSHAKE's `0x04` framing (never
+    // degrade to plain `0x1f` SHAKE), even with an empty customization
+    // string — otherwise an empty-customization TupleHashXOF of a single
+    // pre-encoded element would collide with plain SHAKE of the same
+    // bytes.
+    #[test]
+    fn an_empty_customization_string_still_uses_cshake_framing_not_plain_shake() {
+        #[cfg(feature = "shake")]
+        {
+            let mut got = [0u8; 32];
+            squeeze_all(TupleHashXof256::new(&[]), &[b"one"], &mut got);
+
+            let mut shake = crate::Shake::v256();
+            shake.update(&crate::sp800::encode_string(b"one"));
+            let mut shake_out = [0u8; 32];
+            shake.finalize(&mut shake_out);
+
+            assert_ne!(got, shake_out);
+        }
+    }
+
+    #[test]
+    fn output_length_is_not_bound_into_the_digest() {
+        // TupleHashXOF appends right_encode(0) regardless of how much is
+        // eventually squeezed, so a short squeeze must be a prefix of a
+        // longer one.
+        let mut short_hasher = TupleHashXof128::new(&[]);
+        short_hasher.update_element(b"one");
+        short_hasher.update_element(b"two");
+        let mut short = [0u8; 32];
+        short_hasher.finalize_xof().squeeze(&mut short);
+
+        let mut long_hasher = TupleHashXof128::new(&[]);
+        long_hasher.update_element(b"one");
+        long_hasher.update_element(b"two");
+        let mut long = [0u8; 64];
+        long_hasher.finalize_xof().squeeze(&mut long);
+
+        assert_eq!(short, long[..32]);
+    }
+
+    #[test]
+    fn xof_reader_matches_a_single_large_squeeze() {
+        let mut single_shot = TupleHashXof128::new(b"custom");
+        single_shot.update_element(b"one");
+        single_shot.update_element(b"two");
+        let mut want = [0u8; 300];
+        single_shot.finalize_xof().squeeze(&mut want);
+
+        let mut streamed = TupleHashXof128::new(b"custom");
+        streamed.update_element(b"one");
+        streamed.update_element(b"two");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..17]);
+        reader.squeeze(&mut got[17..]);
+
+        assert_eq!(got, want);
+    }
+}
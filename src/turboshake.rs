@@ -0,0 +1,172 @@
+//! The `TurboSHAKE` extendable-output functions: `SHAKE`'s sponge with the
+//! permutation reduced from 24 to 12 rounds and a caller-chosen
+//! domain-separation byte, as specified in the draft RFC alongside
+//! KangarooTwelve.
+
+use super::{bits_to_rate, keccakf::KeccakF12, Hasher, KeccakState, XofReader};
+
+/// The smallest allowed domain-separation byte for [`TurboShake128::new`]/
+/// [`TurboShake256::new`]. `0x00` is reserved so it can never collide with a
+/// caller's intended domain separator.
+pub const MIN_DOMAIN_SEPARATION_BYTE: u8 = 0x01;
+
+/// The largest allowed domain-separation byte: bit 7 is reserved by the
+/// padding rule, so only 7 usable bits remain.
+pub const MAX_DOMAIN_SEPARATION_BYTE: u8 = 0x7f;
+
+fn check_domain_separation_byte(d: u8) {
+    assert!(
+        (MIN_DOMAIN_SEPARATION_BYTE..=MAX_DOMAIN_SEPARATION_BYTE).contains(&d),
+        "TurboSHAKE domain-separation byte must be in 0x01..=0x7f",
+    );
+}
+
+macro_rules! turboshake {
+    ($name:ident, $reader:ident, $doc:expr, $bits:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            state: KeccakState<KeccakF12>,
+        }
+
+        impl $name {
+            /// Creates a new hasher with domain-separation byte `d`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `d` is outside [`MIN_DOMAIN_SEPARATION_BYTE`]..=
+            /// [`MAX_DOMAIN_SEPARATION_BYTE`].
+            pub fn new(d: u8) -> Self {
+                check_domain_separation_byte(d);
+                $name {
+                    state: KeccakState::new(bits_to_rate($bits), d),
+                }
+            }
+
+            /// Pads the absorbed input and returns a reader that squeezes
+            /// output in a sequence of calls instead of one fixed-size
+            /// buffer.
+            #[doc(alias = "into_xof")]
+            pub fn finalize_xof(self) -> $reader {
+                $reader(XofReader::new(self.state))
+            }
+        }
+
+        impl Hasher for $name {
+            fn update(&mut self, input: &[u8]) {
+                self.state.update(input);
+            }
+
+            fn finalize(self, output: &mut [u8]) {
+                self.state.finalize(output);
+            }
+
+            fn reset(&mut self) {
+                self.state.reset();
+            }
+
+            fn finalize_reset(&mut self, output: &mut [u8]) {
+                self.state.finalize_reset(output);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        crate::impl_io_write!($name);
+        crate::impl_fmt_write!($name);
+        crate::impl_debug_via_state!($name);
+        crate::impl_xof!($name, $reader);
+
+        #[doc = concat!("An extendable-output reader returned by [`", stringify!($name), "::finalize_xof`].")]
+        #[derive(Clone)]
+        pub struct $reader(XofReader<KeccakF12>);
+
+        impl $reader {
+            /// Squeezes `buf.len()` more bytes, continuing from wherever the
+            /// previous `squeeze` call (if any) left off.
+            pub fn squeeze(&mut self, buf: &mut [u8]) {
+                self.0.squeeze(buf);
+            }
+        }
+    };
+}
+
+turboshake!(
+    TurboShake128,
+    TurboShake128Reader,
+    "`TurboSHAKE128`: the 12-round, 128-bit-security member of the TurboSHAKE family.",
+    128
+);
+turboshake!(
+    TurboShake256,
+    TurboShake256Reader,
+    "`TurboSHAKE256`: the 12-round, 256-bit-security member of the TurboSHAKE family.",
+    256
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "TurboSHAKE domain-separation byte must be in 0x01..=0x7f")]
+    fn rejects_zero_domain_separation_byte() {
+        TurboShake128::new(0x00);
+    }
+
+    #[test]
+    #[should_panic(expected = "TurboSHAKE domain-separation byte must be in 0x01..=0x7f")]
+    fn rejects_domain_separation_byte_with_reserved_top_bit() {
+        TurboShake128::new(0x80);
+    }
+
+    #[test]
+    fn different_domain_separation_bytes_diverge() {
+        let mut a = TurboShake128::new(0x01);
+        let mut b = TurboShake128::new(0x1f);
+        a.update(b"hello");
+        b.update(b"hello");
+
+        let mut out_a = [0u8; 32];
+        let mut out_b = [0u8; 32];
+        a.finalize(&mut out_a);
+        b.finalize(&mut out_b);
+        assert_ne!(out_a, out_b);
+    }
+
+    // TurboSHAKE reuses SHAKE's rate/security-level relationship, only the
+    // round count and delimiter differ, so a chunked XOF squeeze must still
+    // match one single-shot squeeze of the same total length.
+    #[test]
+    fn xof_reader_matches_single_shot() {
+        let mut single_shot = TurboShake256::new(0x1f);
+        single_shot.update(b"hello");
+        let mut want = [0u8; 300];
+        single_shot.finalize(&mut want);
+
+        let mut streamed = TurboShake256::new(0x1f);
+        streamed.update(b"hello");
+        let mut reader = streamed.finalize_xof();
+        let mut got = [0u8; 300];
+        reader.squeeze(&mut got[..17]);
+        reader.squeeze(&mut got[17..136]);
+        reader.squeeze(&mut got[136..]);
+
+        assert_eq!(got, want);
+    }
+
+    #[cfg(feature = "shake")]
+    #[test]
+    fn twelve_rounds_produces_a_different_digest_than_shake() {
+        let mut turbo = TurboShake128::new(0x1f);
+        turbo.update(b"hello");
+        let mut turbo_out = [0u8; 32];
+        turbo.finalize(&mut turbo_out);
+
+        let mut shake = crate::Shake::v128();
+        shake.update(b"hello");
+        let mut shake_out = [0u8; 32];
+        shake.finalize(&mut shake_out);
+
+        assert_ne!(turbo_out, shake_out);
+    }
+}
@@ -0,0 +1,242 @@
+//! Known-answer test vectors, exposed so downstream crates can run them as
+//! part of their own test suites instead of duplicating the byte arrays.
+//!
+//! Only SHA3, SHAKE, and the original (pre-standardization, `delim = 0x01`)
+//! Keccak are covered: these are the official/widely-attested sample values
+//! this crate's own unit tests already check against (see
+//! `sha3.rs`/`shake.rs`/`keccak.rs`). `cSHAKE`/`KMAC` are deliberately left
+//! out — this crate's cSHAKE/KMAC implementations haven't themselves been
+//! checked against NIST's SP800-185 sample values (see the caveat at the
+//! top of `cshake.rs`/`kmac.rs`), so shipping "known-answer" vectors for
+//! them here would overstate how validated they are.
+
+use crate::{sha3_224, sha3_256, sha3_384, sha3_512};
+#[cfg(any(feature = "shake", feature = "keccak"))]
+use crate::Hasher;
+#[cfg(feature = "shake")]
+use crate::Shake;
+#[cfg(feature = "keccak")]
+use crate::Keccak;
+
+/// One `(input, expected output)` pair.
+pub struct Vector {
+    pub input: &'static [u8],
+    pub output: &'static [u8],
+}
+
+/// FIPS-202 SHA3-224 samples.
+pub const SHA3_224: &[Vector] = &[
+    Vector {
+        input: b"",
+        output: &[
+            0x6b, 0x4e, 0x03, 0x42, 0x36, 0x67, 0xdb, 0xb7, 0x3b, 0x6e, 0x15, 0x45, 0x4f, 0x0e,
+            0xb1, 0xab, 0xd4, 0x59, 0x7f, 0x9a, 0x1b, 0x07, 0x8e, 0x3f, 0x5b, 0x5a, 0x6b, 0xc7,
+        ],
+    },
+    Vector {
+        input: b"hello",
+        output: &[
+            0xb8, 0x7f, 0x88, 0xc7, 0x27, 0x02, 0xff, 0xf1, 0x74, 0x8e, 0x58, 0xb8, 0x7e, 0x91, 0x41,
+            0xa4, 0x2c, 0x0d, 0xbe, 0xdc, 0x29, 0xa7, 0x8c, 0xb0, 0xd4, 0xa5, 0xcd, 0x81,
+        ],
+    },
+];
+
+/// FIPS-202 SHA3-256 samples.
+pub const SHA3_256: &[Vector] = &[
+    Vector {
+        input: b"",
+        output: &[
+            0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61,
+            0xd6, 0x62, 0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b,
+            0x80, 0xf8, 0x43, 0x4a,
+        ],
+    },
+    Vector {
+        input: b"hello",
+        output: &[
+            0x33, 0x38, 0xbe, 0x69, 0x4f, 0x50, 0xc5, 0xf3, 0x38, 0x81, 0x49, 0x86, 0xcd, 0xf0,
+            0x68, 0x64, 0x53, 0xa8, 0x88, 0xb8, 0x4f, 0x42, 0x4d, 0x79, 0x2a, 0xf4, 0xb9, 0x20,
+            0x23, 0x98, 0xf3, 0x92,
+        ],
+    },
+];
+
+/// FIPS-202 SHA3-384 samples.
+pub const SHA3_384: &[Vector] = &[
+    Vector {
+        input: b"",
+        output: &[
+            0x0c, 0x63, 0xa7, 0x5b, 0x84, 0x5e, 0x4f, 0x7d, 0x01, 0x10, 0x7d, 0x85, 0x2e, 0x4c,
+            0x24, 0x85, 0xc5, 0x1a, 0x50, 0xaa, 0xaa, 0x94, 0xfc, 0x61, 0x99, 0x5e, 0x71, 0xbb,
+            0xee, 0x98, 0x3a, 0x2a, 0xc3, 0x71, 0x38, 0x31, 0x26, 0x4a, 0xdb, 0x47, 0xfb, 0x6b,
+            0xd1, 0xe0, 0x58, 0xd5, 0xf0, 0x04,
+        ],
+    },
+    Vector {
+        input: b"hello",
+        output: &[
+            0x72, 0x0a, 0xea, 0x11, 0x01, 0x9e, 0xf0, 0x64, 0x40, 0xfb, 0xf0, 0x5d, 0x87, 0xaa, 0x24,
+            0x68, 0x0a, 0x21, 0x53, 0xdf, 0x39, 0x07, 0xb2, 0x36, 0x31, 0xe7, 0x17, 0x7c, 0xe6, 0x20,
+            0xfa, 0x13, 0x30, 0xff, 0x07, 0xc0, 0xfd, 0xde, 0xe5, 0x46, 0x99, 0xa4, 0xc3, 0xee, 0x0e,
+            0xe9, 0xd8, 0x87,
+        ],
+    },
+];
+
+/// FIPS-202 SHA3-512 samples.
+pub const SHA3_512: &[Vector] = &[
+    Vector {
+        input: b"",
+        output: &[
+            0xa6, 0x9f, 0x73, 0xcc, 0xa2, 0x3a, 0x9a, 0xc5, 0xc8, 0xb5, 0x67, 0xdc, 0x18, 0x5a,
+            0x75, 0x6e, 0x97, 0xc9, 0x82, 0x16, 0x4f, 0xe2, 0x58, 0x59, 0xe0, 0xd1, 0xdc, 0xc1,
+            0x47, 0x5c, 0x80, 0xa6, 0x15, 0xb2, 0x12, 0x3a, 0xf1, 0xf5, 0xf9, 0x4c, 0x11, 0xe3,
+            0xe9, 0x40, 0x2c, 0x3a, 0xc5, 0x58, 0xf5, 0x00, 0x19, 0x9d, 0x95, 0xb6, 0xd3, 0xe3,
+            0x01, 0x75, 0x85, 0x86, 0x28, 0x1d, 0xcd, 0x26,
+        ],
+    },
+    Vector {
+        input: b"hello",
+        output: &[
+            0x75, 0xd5, 0x27, 0xc3, 0x68, 0xf2, 0xef, 0xe8, 0x48, 0xec, 0xf6, 0xb0, 0x73, 0xa3, 0x67,
+            0x67, 0x80, 0x08, 0x05, 0xe9, 0xee, 0xf2, 0xb1, 0x85, 0x7d, 0x5f, 0x98, 0x4f, 0x03, 0x6e,
+            0xb6, 0xdf, 0x89, 0x1d, 0x75, 0xf7, 0x2d, 0x9b, 0x15, 0x45, 0x18, 0xc1, 0xcd, 0x58, 0x83,
+            0x52, 0x86, 0xd1, 0xda, 0x9a, 0x38, 0xde, 0xba, 0x3d, 0xe9, 0x8b, 0x5a, 0x53, 0xe5, 0xed,
+            0x78, 0xa8, 0x49, 0x76,
+        ],
+    },
+];
+
+/// Well-known Keccak256("") / Keccak512("") values (original `delim = 0x01`
+/// Keccak, distinct from the later `delim = 0x06` SHA3 standardization —
+/// these are the values e.g. Ethereum's `keccak256` refers to).
+#[cfg(feature = "keccak")]
+pub const KECCAK256: &[Vector] = &[Vector {
+    input: b"",
+    output: &[
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ],
+}];
+
+/// See [`KECCAK256`].
+#[cfg(feature = "keccak")]
+pub const KECCAK512: &[Vector] = &[Vector {
+    input: b"",
+    output: &[
+        0x0e, 0xab, 0x42, 0xde, 0x4c, 0x3c, 0xeb, 0x92, 0x35, 0xfc, 0x91, 0xac, 0xff, 0xe7, 0x46,
+        0xb2, 0x9c, 0x29, 0xa8, 0xc3, 0x66, 0xb7, 0xc6, 0x0e, 0x4e, 0x67, 0xc4, 0x66, 0xf3, 0x6a,
+        0x43, 0x04, 0xc0, 0x0f, 0xa9, 0xca, 0xf9, 0xd8, 0x79, 0x76, 0xba, 0x46, 0x9b, 0xcb, 0xe0,
+        0x67, 0x13, 0xb4, 0x35, 0xf0, 0x91, 0xef, 0x27, 0x69, 0xfb, 0x16, 0x0c, 0xda, 0xb3, 0x3d,
+        0x36, 0x70, 0x68, 0x0e,
+    ],
+}];
+
+/// FIPS-202 SHAKE128(""), first 32 output bytes.
+#[cfg(feature = "shake")]
+pub const SHAKE128: &[Vector] = &[Vector {
+    input: b"",
+    output: &[
+        0x7f, 0x9c, 0x2b, 0xa4, 0xe8, 0x8f, 0x82, 0x7d, 0x61, 0x60, 0x45, 0x50, 0x76, 0x05, 0x85,
+        0x3e, 0xd7, 0x3b, 0x80, 0x93, 0xf6, 0xef, 0xbc, 0x88, 0xeb, 0x1a, 0x6e, 0xac, 0xfa, 0x66,
+        0xef, 0x26,
+    ],
+}];
+
+/// FIPS-202 SHAKE256(""), first 32 output bytes.
+#[cfg(feature = "shake")]
+pub const SHAKE256: &[Vector] = &[Vector {
+    input: b"",
+    output: &[
+        0x46, 0xb9, 0xdd, 0x2b, 0x0b, 0xa8, 0x8d, 0x13, 0x23, 0x3b, 0x3f, 0xeb, 0x74, 0x3e, 0xeb,
+        0x24, 0x3f, 0xcd, 0x52, 0xea, 0x62, 0xb8, 0x1b, 0x82, 0xb5, 0x0c, 0x27, 0x64, 0x6e, 0xd5,
+        0x76, 0x2f,
+    ],
+}];
+
+/// Names which vector array and which index within it failed to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The vector array's name, e.g. `"SHA3-256"`.
+    pub name: &'static str,
+    /// The index within that array of the failing vector.
+    pub index: usize,
+}
+
+fn check(name: &'static str, vectors: &[Vector], hash: impl Fn(&[u8]) -> std::vec::Vec<u8>) -> Result<(), Mismatch> {
+    for (index, vector) in vectors.iter().enumerate() {
+        if hash(vector.input) != vector.output {
+            return Err(Mismatch { name, index });
+        }
+    }
+    Ok(())
+}
+
+/// Runs every vector in this module against this crate's own hashers,
+/// returning the first mismatch found, if any.
+pub fn run_all() -> Result<(), Mismatch> {
+    check("SHA3-224", SHA3_224, |input| sha3_224(input).to_vec())?;
+    check("SHA3-256", SHA3_256, |input| sha3_256(input).to_vec())?;
+    check("SHA3-384", SHA3_384, |input| sha3_384(input).to_vec())?;
+    check("SHA3-512", SHA3_512, |input| sha3_512(input).to_vec())?;
+
+    #[cfg(feature = "shake")]
+    {
+        check("SHAKE128", SHAKE128, |input| {
+            let mut shake = Shake::v128();
+            shake.update(input);
+            let mut output = [0u8; 32];
+            shake.finalize(&mut output);
+            output.to_vec()
+        })?;
+        check("SHAKE256", SHAKE256, |input| {
+            let mut shake = Shake::v256();
+            shake.update(input);
+            let mut output = [0u8; 32];
+            shake.finalize(&mut output);
+            output.to_vec()
+        })?;
+    }
+
+    #[cfg(feature = "keccak")]
+    {
+        check("KECCAK256", KECCAK256, |input| {
+            let mut keccak = Keccak::v256();
+            keccak.update(input);
+            let mut output = [0u8; 32];
+            keccak.finalize(&mut output);
+            output.to_vec()
+        })?;
+        check("KECCAK512", KECCAK512, |input| {
+            let mut keccak = Keccak::v512();
+            keccak.update(input);
+            let mut output = [0u8; 64];
+            keccak.finalize(&mut output);
+            output.to_vec()
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_all_passes_against_this_crate_own_hashers() {
+        assert_eq!(run_all(), Ok(()));
+    }
+
+    #[test]
+    fn a_tampered_vector_is_reported_by_name_and_index() {
+        let tampered = [Vector {
+            input: b"hello",
+            output: &[0u8; 32],
+        }];
+        let result = check("SHA3-256", &tampered, |input| sha3_256(input).to_vec());
+        assert_eq!(result, Err(Mismatch { name: "SHA3-256", index: 0 }));
+    }
+}